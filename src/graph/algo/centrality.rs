@@ -0,0 +1,454 @@
+use crate::graph::algo::path::ShortestPath;
+use crate::graph::weight::Weight;
+use crate::graph::{Graph, WeightedGraph};
+use std::collections::VecDeque;
+
+// 出度为0的节点的 PageRank 值如何分配
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dangling {
+    // 平均分配给所有节点
+    Uniform,
+    // 保留给自己, 相当于节点上有一个自环
+    SelfLoop,
+}
+
+pub struct PageRankConfig {
+    // 阻尼系数, 沿着边继续访问的概率
+    pub damping: f64,
+    // 两次迭代结果的差值(L1距离)小于 tolerance 时停止迭代
+    pub tolerance: f64,
+    pub max_iterations: usize,
+    pub dangling: Dangling,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        Self {
+            damping: 0.85,
+            tolerance: 1e-10,
+            max_iterations: 100,
+            dangling: Dangling::Uniform,
+        }
+    }
+}
+
+// PageRank, 返回每个节点的权重, 所有节点的权重之和为1
+pub fn pagerank<G: Graph>(graph: &G, config: &PageRankConfig) -> Vec<f64> {
+    let n = graph.v();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let d = config.damping;
+    let adj: Vec<Vec<usize>> = (0..n).map(|v| graph.adj(v)).collect();
+    let mut rank = vec![1.0 / n as f64; n];
+
+    for _ in 0..config.max_iterations {
+        let mut next = vec![(1.0 - d) / n as f64; n];
+
+        for v in 0..n {
+            if adj[v].is_empty() {
+                match config.dangling {
+                    Dangling::Uniform => {
+                        let share = d * rank[v] / n as f64;
+                        next.iter_mut().for_each(|r| *r += share);
+                    }
+                    Dangling::SelfLoop => next[v] += d * rank[v],
+                }
+            } else {
+                // 平行边会按照边的条数分配
+                let share = d * rank[v] / adj[v].len() as f64;
+                for &w in adj[v].iter() {
+                    next[w] += share;
+                }
+            }
+        }
+
+        let err: f64 = next
+            .iter()
+            .zip(rank.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+        rank = next;
+        if err < config.tolerance {
+            break;
+        }
+    }
+    rank
+}
+
+fn normalize_degree(degree: Vec<usize>) -> Vec<f64> {
+    let n = degree.len();
+    if n <= 1 {
+        return vec![1.0; n];
+    }
+    degree
+        .into_iter()
+        .map(|d| d as f64 / (n - 1) as f64)
+        .collect()
+}
+
+// 出度中心性, 出度除以 n-1
+pub fn out_degree_centrality<G: Graph>(graph: &G) -> Vec<f64> {
+    normalize_degree((0..graph.v()).map(|v| graph.adj(v).len()).collect())
+}
+
+// 入度中心性, 入度除以 n-1
+pub fn in_degree_centrality<G: Graph>(graph: &G) -> Vec<f64> {
+    let mut degree = vec![0; graph.v()];
+    for v in 0..graph.v() {
+        for w in graph.adj(v) {
+            degree[w] += 1;
+        }
+    }
+    normalize_degree(degree)
+}
+
+// 度中心性, 无向图为度数除以 n-1, 有向图为入度与出度之和除以 n-1
+pub fn degree_centrality<G: Graph>(graph: &G) -> Vec<f64> {
+    if !graph.is_directed() {
+        return out_degree_centrality(graph);
+    }
+
+    let out_degree = out_degree_centrality(graph);
+    let in_degree = in_degree_centrality(graph);
+    out_degree
+        .iter()
+        .zip(in_degree.iter())
+        .map(|(a, b)| a + b)
+        .collect()
+}
+
+// 根据从v出发可以到达的节点个数和距离之和计算接近中心性
+// 图不连通时乘以 (可达节点数 - 1) / (n - 1) 进行修正 (Wasserman-Faust)
+fn closeness(n: usize, reachable: usize, total: f64) -> f64 {
+    if total == 0.0 || n <= 1 {
+        return 0.0;
+    }
+    let r = (reachable - 1) as f64;
+    (r / total) * (r / (n - 1) as f64)
+}
+
+// 接近中心性, 使用广度优先遍历计算从v出发到其他节点的最短距离
+pub fn closeness_centrality<G: Graph>(graph: &G) -> Vec<f64> {
+    let n = graph.v();
+    let mut ret = Vec::with_capacity(n);
+    for v in 0..n {
+        let mut bfs = ShortestPath::new(graph, v);
+        let lengths: Vec<usize> = (0..n).filter_map(|w| bfs.length(w)).collect();
+        let total: usize = lengths.iter().sum();
+        ret.push(closeness(n, lengths.len(), total as f64));
+    }
+    ret
+}
+
+// 有权图的接近中心性, 使用 Dijkstra 计算最短距离
+pub fn weighted_closeness_centrality<W, G>(graph: &G) -> Vec<f64>
+where
+    W: Weight,
+    G: WeightedGraph<W>,
+{
+    let n = graph.v();
+    let mut ret = Vec::with_capacity(n);
+    for v in 0..n {
+        let sp = dijkstra(graph, v);
+        let total: f64 = sp.order.iter().map(|&w| sp.dist[w].unwrap().to_f64()).sum();
+        ret.push(closeness(n, sp.order.len(), total));
+    }
+    ret
+}
+
+// 单源最短路径的计算结果, 用于 Brandes 算法
+struct ShortestPaths<W> {
+    // 按照距离从小到大的顺序访问到的节点
+    order: Vec<usize>,
+    dist: Vec<Option<W>>,
+    // pred[w] 表示所有最短路径中w的前一个节点
+    pred: Vec<Vec<usize>>,
+    // sigma[w] 表示从起点到w的最短路径条数
+    sigma: Vec<f64>,
+}
+
+fn bfs<G: Graph>(graph: &G, s: usize) -> ShortestPaths<usize> {
+    let n = graph.v();
+    let mut sp = ShortestPaths {
+        order: Vec::with_capacity(n),
+        dist: vec![None; n],
+        pred: vec![vec![]; n],
+        sigma: vec![0.0; n],
+    };
+    sp.dist[s] = Some(0);
+    sp.sigma[s] = 1.0;
+
+    let mut queue = VecDeque::new();
+    queue.push_back(s);
+    while let Some(v) = queue.pop_front() {
+        sp.order.push(v);
+        let d = sp.dist[v].unwrap();
+        for w in graph.adj(v) {
+            if sp.dist[w].is_none() {
+                sp.dist[w] = Some(d + 1);
+                queue.push_back(w);
+            }
+            if sp.dist[w] == Some(d + 1) {
+                sp.sigma[w] += sp.sigma[v];
+                sp.pred[w].push(v);
+            }
+        }
+    }
+    sp
+}
+
+// O(V^2 + E) 的 Dijkstra, 需要记录所有的最短路径
+fn dijkstra<W, G>(graph: &G, s: usize) -> ShortestPaths<W>
+where
+    W: Weight,
+    G: WeightedGraph<W>,
+{
+    let n = graph.v();
+    let mut sp = ShortestPaths {
+        order: Vec::with_capacity(n),
+        dist: vec![None; n],
+        pred: vec![vec![]; n],
+        sigma: vec![0.0; n],
+    };
+    sp.dist[s] = Some(W::zero());
+    sp.sigma[s] = 1.0;
+
+    let mut done = vec![false; n];
+    loop {
+        // 选择未完成的节点中距离最小的节点
+        let mut next: Option<usize> = None;
+        for (v, dist) in sp.dist.iter().enumerate() {
+            if let (false, Some(d)) = (done[v], dist) {
+                if next.is_none_or(|u| *d < sp.dist[u].unwrap()) {
+                    next = Some(v);
+                }
+            }
+        }
+        let v = match next {
+            Some(v) => v,
+            None => break,
+        };
+        done[v] = true;
+        sp.order.push(v);
+
+        let d = sp.dist[v].unwrap();
+        for edge in graph.adj(v) {
+            let w = edge.other(v);
+            if done[w] {
+                continue;
+            }
+            let alt = d + edge.weight();
+            match sp.dist[w] {
+                Some(old) if alt > old => (),
+                Some(old) if alt == old => {
+                    sp.sigma[w] += sp.sigma[v];
+                    sp.pred[w].push(v);
+                }
+                _ => {
+                    sp.dist[w] = Some(alt);
+                    sp.sigma[w] = sp.sigma[v];
+                    sp.pred[w] = vec![v];
+                }
+            }
+        }
+    }
+    sp
+}
+
+// Brandes 算法的依赖累积, 按照距离从大到小的顺序回溯
+fn accumulate<W>(sp: &ShortestPaths<W>, s: usize, centrality: &mut [f64]) {
+    let mut delta = vec![0.0; centrality.len()];
+    for &w in sp.order.iter().rev() {
+        for &v in sp.pred[w].iter() {
+            delta[v] += sp.sigma[v] / sp.sigma[w] * (1.0 + delta[w]);
+        }
+        if w != s {
+            centrality[w] += delta[w];
+        }
+    }
+}
+
+// 介数中心性 - Brandes 算法, O(VE)
+// 无向图中每条路径会从两个方向各统计一次, 结果除以2
+pub fn betweenness_centrality<G: Graph>(graph: &G) -> Vec<f64> {
+    let n = graph.v();
+    let mut centrality = vec![0.0; n];
+    for s in 0..n {
+        let sp = bfs(graph, s);
+        accumulate(&sp, s, &mut centrality);
+    }
+
+    if !graph.is_directed() {
+        centrality.iter_mut().for_each(|c| *c /= 2.0);
+    }
+    centrality
+}
+
+// 有权图的介数中心性 - Brandes 算法, O(V^3)
+pub fn weighted_betweenness_centrality<W, G>(graph: &G) -> Vec<f64>
+where
+    W: Weight,
+    G: WeightedGraph<W>,
+{
+    let n = graph.v();
+    let mut centrality = vec![0.0; n];
+    for s in 0..n {
+        let sp = dijkstra(graph, s);
+        accumulate(&sp, s, &mut centrality);
+    }
+
+    if !graph.is_directed() {
+        centrality.iter_mut().for_each(|c| *c /= 2.0);
+    }
+    centrality
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::weight::OrderedF64;
+    use crate::graph::{
+        readgraph, DenseGraph, DenseWeightedGraph, SparseGraph, SparseWeightedGraph,
+    };
+    use std::path::Path;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < 1e-6, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn pagerank_cycle() {
+        // 有向环上所有节点的权重相同
+        let mut g = SparseGraph::new(4, true);
+        for i in 0..4 {
+            g.add_edge(i, (i + 1) % 4);
+        }
+        let rank = pagerank(&g, &PageRankConfig::default());
+        assert_close(&rank, &[0.25; 4]);
+    }
+
+    #[test]
+    fn pagerank_dangling() {
+        // 0 -> 1, 0 -> 2, 1 -> 2, 2 没有出边
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(1, 2);
+
+        let rank = pagerank(&g, &PageRankConfig::default());
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(rank[2] > rank[1] && rank[1] > rank[0]);
+
+        let config = PageRankConfig {
+            dangling: Dangling::SelfLoop,
+            ..PageRankConfig::default()
+        };
+        let self_loop = pagerank(&g, &config);
+        assert!((self_loop.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(self_loop[2] > rank[2]);
+
+        // 阻尼系数为0时所有节点权重相同
+        let config = PageRankConfig {
+            damping: 0.0,
+            ..PageRankConfig::default()
+        };
+        assert_close(&pagerank(&g, &config), &[1.0 / 3.0; 3]);
+    }
+
+    #[test]
+    fn degree() {
+        // 星型图
+        let mut g = DenseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(0, 3);
+        assert_close(
+            &degree_centrality(&g),
+            &[1.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0],
+        );
+
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(1, 2);
+        assert_close(&out_degree_centrality(&g), &[1.0, 0.5, 0.0]);
+        assert_close(&in_degree_centrality(&g), &[0.0, 0.5, 1.0]);
+        assert_close(&degree_centrality(&g), &[1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn closeness_bfs_and_dijkstra() {
+        // 路径 0 - 1 - 2 - 3
+        let mut g = SparseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        assert_close(&closeness_centrality(&g), &[0.5, 0.75, 0.75, 0.5]);
+
+        let mut wg: SparseWeightedGraph<u32> = SparseWeightedGraph::new(4, false);
+        wg.add_edge(0, 1, 1);
+        wg.add_edge(1, 2, 1);
+        wg.add_edge(2, 3, 1);
+        assert_close(&weighted_closeness_centrality(&wg), &[0.5, 0.75, 0.75, 0.5]);
+
+        // 不连通的图
+        let mut g = SparseGraph::new(4, false);
+        g.add_edge(0, 1);
+        assert_close(&closeness_centrality(&g), &[1.0 / 3.0, 1.0 / 3.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn betweenness() {
+        // 路径 0 - 1 - 2 - 3
+        let mut g = SparseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        assert_close(&betweenness_centrality(&g), &[0.0, 2.0, 2.0, 0.0]);
+
+        // 4个节点的环, 每对对角节点之间有2条最短路径
+        let mut g = SparseGraph::new(4, false);
+        for i in 0..4 {
+            g.add_edge(i, (i + 1) % 4);
+        }
+        assert_close(&betweenness_centrality(&g), &[0.5; 4]);
+
+        // 有向图
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        assert_close(&betweenness_centrality(&g), &[0.0, 1.0, 0.0]);
+
+        // test2.txt 中 0 是唯一连接 1, 2 和其他节点的节点
+        let filename = Path::new("./src/files/graph/test2.txt");
+        let mut g = SparseGraph::new(7, false);
+        readgraph::read(&mut g, filename).unwrap();
+        let c = betweenness_centrality(&g);
+        assert!((0..7).all(|v| c[0] >= c[v]));
+        assert_eq!(c[1], 0.0);
+    }
+
+    #[test]
+    fn weighted_betweenness() {
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(3, false);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 2, 5);
+        assert_close(&weighted_betweenness_centrality(&g), &[0.0, 1.0, 0.0]);
+
+        // 0 到 2 有两条最短路径
+        g.add_edge(0, 2, 2);
+        assert_close(&weighted_betweenness_centrality(&g), &[0.0, 0.5, 0.0]);
+
+        let mut g: SparseWeightedGraph<OrderedF64> = SparseWeightedGraph::new(3, true);
+        g.add_edge(0, 1, OrderedF64::new(0.5));
+        g.add_edge(1, 2, OrderedF64::new(0.5));
+        g.add_edge(0, 2, OrderedF64::new(2.0));
+        assert_close(&weighted_betweenness_centrality(&g), &[0.0, 1.0, 0.0]);
+    }
+}
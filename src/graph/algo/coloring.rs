@@ -0,0 +1,232 @@
+use crate::graph::Graph;
+use std::collections::HashSet;
+
+// 图的着色结果, 颜色从0开始编号
+#[derive(Debug)]
+pub struct Coloring {
+    colors: Vec<usize>,
+    count: usize,
+}
+
+impl Coloring {
+    fn from_colors(colors: Vec<usize>) -> Self {
+        let count = colors.iter().map(|&c| c + 1).max().unwrap_or(0);
+        Self { colors, count }
+    }
+
+    // 返回节点v的颜色
+    pub fn color(&self, v: usize) -> usize {
+        self.colors[v]
+    }
+
+    // 返回所有节点的颜色
+    pub fn colors(&self) -> &[usize] {
+        &self.colors
+    }
+
+    // 返回使用的颜色个数
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// 无向化并去重之后的邻接表, 忽略自环
+fn neighbors<G: Graph>(graph: &G) -> Vec<Vec<usize>> {
+    let n = graph.v();
+    let mut adj = vec![vec![]; n];
+    for v in 0..n {
+        for w in graph.adj(v) {
+            if v != w {
+                adj[v].push(w);
+                adj[w].push(v);
+            }
+        }
+    }
+
+    for list in adj.iter_mut() {
+        list.sort_unstable();
+        list.dedup();
+    }
+    adj
+}
+
+// 为v选择相邻节点没有使用过的最小颜色
+fn smallest_free_color(adj: &[usize], colors: &[Option<usize>]) -> usize {
+    let used: HashSet<usize> = adj.iter().filter_map(|&w| colors[w]).collect();
+    (0..).find(|c| !used.contains(c)).unwrap()
+}
+
+// 贪心着色, 按照给定的顺序依次为节点选择最小的可用颜色
+// order 必须是所有节点的一个排列
+pub fn greedy<G: Graph>(graph: &G, order: &[usize]) -> Coloring {
+    let n = graph.v();
+    assert_eq!(order.len(), n);
+
+    let adj = neighbors(graph);
+    let mut colors = vec![None; n];
+    for &v in order.iter() {
+        assert!(colors[v].is_none());
+        colors[v] = Some(smallest_free_color(&adj[v], &colors));
+    }
+
+    Coloring::from_colors(colors.into_iter().map(|c| c.unwrap()).collect())
+}
+
+// Welsh-Powell 算法, 按照度数从大到小的顺序贪心着色
+pub fn welsh_powell<G: Graph>(graph: &G) -> Coloring {
+    let adj = neighbors(graph);
+    let mut order: Vec<usize> = (0..graph.v()).collect();
+    order.sort_by(|&a, &b| adj[b].len().cmp(&adj[a].len()));
+    greedy(graph, &order)
+}
+
+// DSatur 算法, 每次选择饱和度最大的节点着色
+// 饱和度为相邻节点中已经使用的不同颜色个数, 相同时选择度数最大的节点
+pub fn dsatur<G: Graph>(graph: &G) -> Coloring {
+    let n = graph.v();
+    let adj = neighbors(graph);
+    let mut colors = vec![None; n];
+    // saturation[v] 保存v的相邻节点已经使用的颜色
+    let mut saturation: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+
+    for _ in 0..n {
+        let v = (0..n)
+            .filter(|&v| colors[v].is_none())
+            .max_by(|&a, &b| {
+                saturation[a]
+                    .len()
+                    .cmp(&saturation[b].len())
+                    .then(adj[a].len().cmp(&adj[b].len()))
+                    // 都相同时选择编号最小的节点
+                    .then(b.cmp(&a))
+            })
+            .unwrap();
+
+        let c = smallest_free_color(&adj[v], &colors);
+        colors[v] = Some(c);
+        for &w in adj[v].iter() {
+            saturation[w].insert(c);
+        }
+    }
+
+    Coloring::from_colors(colors.into_iter().map(|c| c.unwrap()).collect())
+}
+
+// 检查着色是否合法, 即任意相邻的两个节点颜色不同, 自环不参与检查
+pub fn is_valid<G: Graph>(graph: &G, colors: &[usize]) -> bool {
+    if colors.len() != graph.v() {
+        return false;
+    }
+
+    for v in 0..graph.v() {
+        for w in graph.adj(v) {
+            if v != w && colors[v] == colors[w] {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{readgraph, DenseGraph, SparseGraph};
+    use crate::util::random;
+    use rand::Rng;
+    use std::path::Path;
+
+    // 皇冠图: u_i 和 v_j (i != j) 之间有边
+    // u_i = 2i, v_i = 2i+1, 按编号顺序贪心着色会使用 n 种颜色
+    fn crown(n: usize) -> SparseGraph {
+        let mut g = SparseGraph::new(2 * n, false);
+        for i in 0..n {
+            for j in 0..n {
+                if i != j {
+                    g.add_edge(2 * i, 2 * j + 1);
+                }
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn greedy_order() {
+        let g = crown(4);
+        let order: Vec<usize> = (0..8).collect();
+        let coloring = greedy(&g, &order);
+        assert!(is_valid(&g, coloring.colors()));
+        assert_eq!(coloring.count(), 4);
+
+        // 先给一侧着色, 只需要2种颜色
+        let order = vec![0, 2, 4, 6, 1, 3, 5, 7];
+        let coloring = greedy(&g, &order);
+        assert_eq!(coloring.count(), 2);
+        assert_eq!(coloring.color(0), coloring.color(6));
+    }
+
+    #[test]
+    fn strategies() {
+        // 奇数环需要3种颜色
+        let mut g = SparseGraph::new(5, false);
+        for i in 0..5 {
+            g.add_edge(i, (i + 1) % 5);
+        }
+        assert_eq!(welsh_powell(&g).count(), 3);
+        assert_eq!(dsatur(&g).count(), 3);
+
+        // 完全图需要n种颜色
+        let mut g = DenseGraph::new(5, false);
+        for i in 0..5 {
+            for j in i + 1..5 {
+                g.add_edge(i, j);
+            }
+        }
+        assert_eq!(welsh_powell(&g).count(), 5);
+        assert_eq!(dsatur(&g).count(), 5);
+
+        // DSatur 对二分图总是得到2种颜色
+        let g = crown(5);
+        assert_eq!(dsatur(&g).count(), 2);
+
+        // test1.txt 是一个森林
+        let filename = Path::new("./src/files/graph/test1.txt");
+        let mut g = SparseGraph::new(13, false);
+        readgraph::read(&mut g, filename).unwrap();
+        let coloring = dsatur(&g);
+        assert!(is_valid(&g, coloring.colors()));
+        assert!(coloring.count() <= 3);
+
+        let g = SparseGraph::new(0, false);
+        assert_eq!(dsatur(&g).count(), 0);
+    }
+
+    #[test]
+    fn random_graphs() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for _ in 0..20 {
+                let n = rng.gen_range(1, 30);
+                let mut g = SparseGraph::new(n, rng.gen());
+                for _ in 0..rng.gen_range(0, 3 * n) {
+                    g.add_edge(rng.gen_range(0, n), rng.gen_range(0, n));
+                }
+
+                let order: Vec<usize> = (0..n).rev().collect();
+                assert!(is_valid(&g, greedy(&g, &order).colors()));
+                assert!(is_valid(&g, welsh_powell(&g).colors()));
+                assert!(is_valid(&g, dsatur(&g).colors()));
+            }
+        });
+    }
+
+    #[test]
+    fn invalid() {
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(2, 2);
+        assert!(!is_valid(&g, &[0, 0, 1]));
+        assert!(is_valid(&g, &[0, 1, 0]));
+        assert!(!is_valid(&g, &[0, 1]));
+    }
+}
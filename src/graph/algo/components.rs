@@ -47,6 +47,21 @@ where
         self.component_count
     }
 
+    // 返回每个联通分量中的节点个数, 下标为联通分量的编号
+    pub fn sizes(&mut self) -> Vec<usize> {
+        let mut sizes = vec![0; self.count()];
+        for id in self.id.iter().flatten() {
+            sizes[*id] += 1;
+        }
+        sizes
+    }
+
+    // 返回每个节点所属的联通分量编号, 编号按照节点首次出现的顺序从0开始
+    pub fn ids(&mut self) -> Vec<usize> {
+        self.count();
+        self.id.iter().map(|id| id.unwrap()).collect()
+    }
+
     pub fn is_connected(&self, v: usize, w: usize) -> bool {
         let max = self.graph.v();
         assert!(v < max && w < max);
@@ -0,0 +1,151 @@
+use crate::graph::Graph;
+use crate::uf::unionfind::uf6::UnionFind;
+
+// 增量连通性, 边只增加不删除时不需要每次重新计算连通分量
+// 基于路径压缩和 rank 优化的并查集, 每次操作接近 O(1)
+pub struct IncrementalConnectivity {
+    uf: UnionFind,
+    n: usize,
+    // size[root] 表示以root为根的集合中的节点个数, 只有根节点的值有效
+    size: Vec<usize>,
+    component_count: usize,
+}
+
+impl IncrementalConnectivity {
+    pub fn new(n: usize) -> Self {
+        Self {
+            uf: UnionFind::new(n),
+            n,
+            size: vec![1; n],
+            component_count: n,
+        }
+    }
+
+    // 通过已有的图构造, 有向图按照弱连通计算
+    pub fn from_graph<G: Graph>(graph: &G) -> Self {
+        let mut ret = Self::new(graph.v());
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                ret.add_edge(v, w);
+            }
+        }
+        ret
+    }
+
+    // 加入边v-w, 合并了两个连通分量时返回 true
+    pub fn add_edge(&mut self, v: usize, w: usize) -> bool {
+        assert!(v < self.n && w < self.n);
+
+        let v_root = self.uf.find(v);
+        let w_root = self.uf.find(w);
+        if v_root == w_root {
+            return false;
+        }
+
+        self.uf.union_elements(v_root, w_root);
+        let root = self.uf.find(v_root);
+        self.size[root] = self.size[v_root] + self.size[w_root];
+        self.component_count -= 1;
+        true
+    }
+
+    pub fn is_connected(&mut self, v: usize, w: usize) -> bool {
+        assert!(v < self.n && w < self.n);
+        self.uf.is_connected(v, w)
+    }
+
+    // 返回连通分量个数
+    pub fn count(&self) -> usize {
+        self.component_count
+    }
+
+    // 返回节点v所在连通分量的节点个数
+    pub fn component_size(&mut self, v: usize) -> usize {
+        assert!(v < self.n);
+        let root = self.uf.find(v);
+        self.size[root]
+    }
+
+    // 返回每个节点所属的连通分量编号, 与 Component::ids 的编号方式一致
+    // 即按照节点编号从小到大, 第一次出现的连通分量依次编号为 0, 1, 2...
+    pub fn snapshot(&mut self) -> Vec<usize> {
+        let mut root_id = vec![None; self.n];
+        let mut next = 0;
+        let mut ids = Vec::with_capacity(self.n);
+        for v in 0..self.n {
+            let root = self.uf.find(v);
+            let id = *root_id[root].get_or_insert_with(|| {
+                next += 1;
+                next - 1
+            });
+            ids.push(id);
+        }
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::algo::components::Component;
+    use crate::graph::{readgraph, SparseGraph};
+    use crate::util::random;
+    use rand::Rng;
+    use std::path::Path;
+
+    #[test]
+    fn incremental() {
+        let mut ic = IncrementalConnectivity::new(5);
+        assert_eq!(ic.count(), 5);
+        assert!(ic.add_edge(0, 1));
+        assert!(ic.add_edge(3, 4));
+        assert!(!ic.add_edge(1, 0));
+        assert_eq!(ic.count(), 3);
+        assert!(ic.is_connected(1, 0));
+        assert!(!ic.is_connected(1, 3));
+        assert_eq!(ic.component_size(4), 2);
+        assert_eq!(ic.component_size(2), 1);
+        assert_eq!(ic.snapshot(), vec![0, 0, 1, 2, 2]);
+
+        assert!(ic.add_edge(2, 4));
+        assert!(ic.add_edge(1, 2));
+        assert_eq!(ic.count(), 1);
+        assert_eq!(ic.component_size(0), 5);
+        assert_eq!(ic.snapshot(), vec![0; 5]);
+    }
+
+    #[test]
+    fn same_as_component() {
+        random::reproducible(|| {
+            let filename = Path::new("./src/files/graph/test1.txt");
+            let mut g = SparseGraph::new(13, false);
+            readgraph::read(&mut g, filename).unwrap();
+
+            let mut ic = IncrementalConnectivity::from_graph(&g);
+            let mut component = Component::new(&g);
+            assert_eq!(ic.count(), component.count());
+            assert_eq!(ic.snapshot(), component.ids());
+
+            let mut rng = random::rng();
+            for _ in 0..20 {
+                let n = rng.gen_range(1, 40);
+                let mut g = SparseGraph::new(n, false);
+                let mut ic = IncrementalConnectivity::new(n);
+                for _ in 0..rng.gen_range(0, n) {
+                    let (v, w) = (rng.gen_range(0, n), rng.gen_range(0, n));
+                    g.add_edge(v, w);
+                    ic.add_edge(v, w);
+                }
+
+                let mut component = Component::new(&g);
+                assert_eq!(ic.count(), component.count());
+                assert_eq!(ic.snapshot(), component.ids());
+                let sizes = component.sizes();
+                let ids = component.ids();
+                for v in 0..n {
+                    assert_eq!(ic.component_size(v), sizes[ids[v]]);
+                }
+            }
+        });
+    }
+}
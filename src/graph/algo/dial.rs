@@ -0,0 +1,170 @@
+use crate::graph::weight::IntegerWeight;
+use crate::graph::WeightedGraph;
+
+// 边的权值为较小的非负整数时的单源最短路径 - Dial 算法, O(E + V * C)
+// C 为最大的边权值, 使用 C + 1 个桶组成的循环队列代替 Dijkstra 中的堆
+// 桶 d % (C + 1) 中存放距离为d的节点, 任意时刻未处理的距离都在 [d, d + C] 之间
+pub struct Dial<'a, W, G>
+where
+    G: WeightedGraph<W>,
+{
+    graph: &'a G,
+    start: usize,
+    dist: Vec<Option<W>>,
+    from: Vec<Option<usize>>,
+    initialized: bool,
+}
+
+impl<'a, W, G> Dial<'a, W, G>
+where
+    W: IntegerWeight,
+    G: WeightedGraph<W>,
+{
+    pub fn new(graph: &'a G, start: usize) -> Self {
+        assert!(start < graph.v());
+        Self {
+            graph,
+            start,
+            dist: vec![None; graph.v()],
+            from: vec![None; graph.v()],
+            initialized: false,
+        }
+    }
+
+    fn dial(&mut self) {
+        let n = self.graph.v();
+        let max_weight = (0..n)
+            .flat_map(|v| self.graph.adj(v).into_iter().map(|e| e.weight().to_usize()))
+            .max()
+            .unwrap_or(0);
+
+        let size = max_weight + 1;
+        let mut buckets: Vec<Vec<usize>> = vec![vec![]; size];
+        let mut marked = vec![false; n];
+        // 所有桶中的节点个数, 同一个节点可能在不同的桶中出现多次
+        let mut pending = 1;
+        self.dist[self.start] = Some(W::zero());
+        buckets[0].push(self.start);
+
+        let mut d = 0;
+        while pending > 0 {
+            let idx = d % size;
+            while let Some(v) = buckets[idx].pop() {
+                pending -= 1;
+                if marked[v] {
+                    continue;
+                }
+                marked[v] = true;
+
+                let dv = self.dist[v].unwrap();
+                for edge in self.graph.adj(v) {
+                    let w = edge.other(v);
+                    let alt = dv + edge.weight();
+                    if !marked[w] && self.dist[w].is_none_or(|old| alt < old) {
+                        self.dist[w] = Some(alt);
+                        self.from[w] = Some(v);
+                        buckets[alt.to_usize() % size].push(w);
+                        pending += 1;
+                    }
+                }
+            }
+            d += 1;
+        }
+    }
+
+    fn initialize(&mut self) {
+        self.dial();
+        self.initialized = true;
+    }
+
+    // 查询从s点到w点是否有路径
+    pub fn has_path(&mut self, w: usize) -> bool {
+        assert!(w < self.graph.v());
+        if !self.initialized {
+            self.initialize();
+        }
+        self.dist[w].is_some()
+    }
+
+    // 查询从s点到w点的最短路径, 存放在vec中
+    pub fn path(&mut self, w: usize, vec: &mut Vec<usize>) {
+        assert!(self.has_path(w));
+
+        let mut stack = Vec::new();
+        let mut p = Some(w);
+        while let Some(v) = p {
+            stack.push(v);
+            p = self.from[v];
+        }
+        while let Some(v) = stack.pop() {
+            vec.push(v);
+        }
+    }
+
+    // 查询从s点到w点的最短路径长度, 不可达时返回 None
+    pub fn length(&mut self, w: usize) -> Option<W> {
+        assert!(w < self.graph.v());
+        if !self.initialized {
+            self.initialize();
+        }
+        self.dist[w]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::algo::dijkstra::Dijkstra;
+    use crate::graph::{DenseWeightedGraph, SparseWeightedGraph};
+    use crate::util::random;
+    use rand::Rng;
+
+    #[test]
+    fn small_weights() {
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(5, true);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 2, 2);
+        g.add_edge(0, 3, 6);
+        g.add_edge(1, 4, 1);
+        g.add_edge(2, 1, 1);
+        g.add_edge(2, 4, 5);
+        g.add_edge(2, 3, 3);
+        g.add_edge(3, 4, 2);
+
+        let mut dial = Dial::new(&g, 0);
+        assert_eq!(dial.length(4), Some(4));
+        assert_eq!(dial.length(3), Some(5));
+        let mut path = Vec::new();
+        dial.path(4, &mut path);
+        assert_eq!(path, vec![0, 2, 1, 4]);
+
+        let mut dial = Dial::new(&g, 4);
+        assert!(!dial.has_path(0));
+        assert_eq!(dial.length(4), Some(0));
+    }
+
+    #[test]
+    fn random_against_dijkstra() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for _ in 0..20 {
+                let n = rng.gen_range(1, 50);
+                let max_weight = rng.gen_range(1, 10);
+                let mut g: SparseWeightedGraph<usize> = SparseWeightedGraph::new(n, rng.gen());
+                for _ in 0..rng.gen_range(0, 4 * n) {
+                    g.add_edge(
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, max_weight + 1),
+                    );
+                }
+
+                let mut dial = Dial::new(&g, 0);
+                let mut dijkstra = Dijkstra::new(&g, 0);
+                for v in 0..n {
+                    assert_eq!(dial.length(v), dijkstra.length(v));
+                }
+            }
+        });
+    }
+}
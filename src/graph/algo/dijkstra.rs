@@ -0,0 +1,171 @@
+use crate::graph::weight::Weight;
+use crate::graph::WeightedGraph;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// 单源最短路径 - Dijkstra 算法, 要求边的权值非负, O(ElogV)
+pub struct Dijkstra<'a, W, G>
+where
+    G: WeightedGraph<W>,
+{
+    graph: &'a G,
+    start: usize,
+    dist: Vec<Option<W>>,
+    from: Vec<Option<usize>>,
+    initialized: bool,
+}
+
+impl<'a, W, G> Dijkstra<'a, W, G>
+where
+    W: Weight,
+    G: WeightedGraph<W>,
+{
+    pub fn new(graph: &'a G, start: usize) -> Self {
+        assert!(start < graph.v());
+        Self {
+            graph,
+            start,
+            dist: vec![None; graph.v()],
+            from: vec![None; graph.v()],
+            initialized: false,
+        }
+    }
+
+    fn dijkstra(&mut self) {
+        let mut marked = vec![false; self.graph.v()];
+        // 最小堆, 同一个节点可能被多次放入, 取出时跳过已经确定的节点
+        let mut pq = BinaryHeap::new();
+        self.dist[self.start] = Some(W::zero());
+        pq.push(Reverse((W::zero(), self.start)));
+
+        while let Some(Reverse((d, v))) = pq.pop() {
+            if marked[v] {
+                continue;
+            }
+            marked[v] = true;
+
+            for edge in self.graph.adj(v) {
+                let w = edge.other(v);
+                let weight = edge.weight();
+                assert!(weight >= W::zero(), "negative weight {}", weight);
+                let alt = d + weight;
+                if !marked[w] && self.dist[w].is_none_or(|old| alt < old) {
+                    self.dist[w] = Some(alt);
+                    self.from[w] = Some(v);
+                    pq.push(Reverse((alt, w)));
+                }
+            }
+        }
+    }
+
+    fn initialize(&mut self) {
+        self.dijkstra();
+        self.initialized = true;
+    }
+
+    // 查询从s点到w点是否有路径
+    pub fn has_path(&mut self, w: usize) -> bool {
+        assert!(w < self.graph.v());
+        if !self.initialized {
+            self.initialize();
+        }
+        self.dist[w].is_some()
+    }
+
+    // 查询从s点到w点的最短路径, 存放在vec中
+    pub fn path(&mut self, w: usize, vec: &mut Vec<usize>) {
+        assert!(self.has_path(w));
+
+        let mut stack = Vec::new();
+        let mut p = Some(w);
+        while let Some(v) = p {
+            stack.push(v);
+            p = self.from[v];
+        }
+        while let Some(v) = stack.pop() {
+            vec.push(v);
+        }
+    }
+
+    // 打印出从s点到w点的最短路径
+    pub fn show_path(&mut self, w: usize) {
+        let mut vec = Vec::new();
+        self.path(w, &mut vec);
+        let path: Vec<String> = vec.iter().map(|v| v.to_string()).collect();
+        println!("{}", path.join(" -> "));
+    }
+
+    // 查询从s点到w点的最短路径长度, 不可达时返回 None
+    pub fn length(&mut self, w: usize) -> Option<W> {
+        assert!(w < self.graph.v());
+        if !self.initialized {
+            self.initialize();
+        }
+        self.dist[w]
+    }
+
+    // 不可达的节点距离为 W::infinity()
+    pub fn distances(&mut self) -> Vec<W> {
+        if !self.initialized {
+            self.initialize();
+        }
+        self.dist
+            .iter()
+            .map(|d| d.unwrap_or_else(W::infinity))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::weight::OrderedF64;
+    use crate::graph::{readgraph, DenseWeightedGraph, SparseWeightedGraph};
+    use std::path::Path;
+
+    #[test]
+    fn shortest_path() {
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(5, true);
+        g.add_edge(0, 1, 5);
+        g.add_edge(0, 2, 2);
+        g.add_edge(0, 3, 6);
+        g.add_edge(1, 4, 1);
+        g.add_edge(2, 1, 1);
+        g.add_edge(2, 4, 5);
+        g.add_edge(2, 3, 3);
+        g.add_edge(3, 4, 2);
+
+        let mut sp = Dijkstra::new(&g, 0);
+        assert_eq!(sp.length(4), Some(4));
+        assert_eq!(sp.length(3), Some(5));
+        let mut path = Vec::new();
+        sp.path(4, &mut path);
+        assert_eq!(path, vec![0, 2, 1, 4]);
+        assert_eq!(sp.distances(), vec![0, 3, 2, 5, 4]);
+
+        let mut sp = Dijkstra::new(&g, 4);
+        assert!(!sp.has_path(0));
+        assert_eq!(sp.distances()[0], u32::MAX);
+    }
+
+    #[test]
+    fn float_weights() {
+        let filename = Path::new("./src/files/graph/test3.txt");
+        let mut g: SparseWeightedGraph<OrderedF64> = SparseWeightedGraph::new(8, false);
+        readgraph::read_weighted_graph(&mut g, filename).unwrap();
+
+        let mut sp = Dijkstra::new(&g, 0);
+        let d = sp.length(6).unwrap();
+        assert!((d.value() - 0.58).abs() < 1e-9);
+        assert!((sp.length(5).unwrap().value() - 0.44).abs() < 1e-9);
+        assert!(sp.has_path(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "negative weight")]
+    fn negative_weight() {
+        let mut g: SparseWeightedGraph<i32> = SparseWeightedGraph::new(2, true);
+        g.add_edge(0, 1, -1);
+        Dijkstra::new(&g, 0).has_path(1);
+    }
+}
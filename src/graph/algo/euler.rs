@@ -0,0 +1,348 @@
+use crate::graph::algo::components::Component;
+use crate::graph::{Graph, SparseGraph};
+
+// 欧拉路径和欧拉回路 - Hierholzer 算法, O(V + E)
+// 支持有向图和无向图, 允许平行边和自环
+pub struct Eulerian<'a, G: Graph> {
+    graph: &'a G,
+    // 每条边的两个端点, 无向图中每条边只出现一次
+    edges: Vec<(usize, usize)>,
+    // 欧拉路径的起点, None 表示不存在欧拉路径
+    start: Option<usize>,
+    circuit: bool,
+    euler: Vec<usize>,
+    initialized: bool,
+}
+
+impl<'a, G> Eulerian<'a, G>
+where
+    G: Graph,
+{
+    pub fn new(graph: &'a G) -> Self {
+        let mut edges = Vec::new();
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                // 无向图中边v-w在v和w的邻接表中各出现一次, 只保留一次
+                if graph.is_directed() || v <= w {
+                    edges.push((v, w));
+                }
+            }
+        }
+
+        Self {
+            graph,
+            edges,
+            start: None,
+            circuit: false,
+            euler: Vec::new(),
+            initialized: false,
+        }
+    }
+
+    // 检查度数条件, 确定欧拉路径的起点
+    fn check_degree(&mut self) {
+        let n = self.graph.v();
+        let mut out_degree = vec![0i64; n];
+        let mut in_degree = vec![0i64; n];
+        for &(v, w) in self.edges.iter() {
+            out_degree[v] += 1;
+            in_degree[w] += 1;
+        }
+
+        // 第一个有边的节点
+        let first = (0..n).find(|&v| out_degree[v] + in_degree[v] > 0);
+
+        if self.graph.is_directed() {
+            // 有向图: 所有节点入度等于出度时存在欧拉回路
+            // 恰好一个节点出度比入度大1, 一个节点入度比出度大1时存在欧拉路径
+            let mut start = None;
+            let mut end = None;
+            for v in 0..n {
+                match out_degree[v] - in_degree[v] {
+                    0 => (),
+                    1 if start.is_none() => start = Some(v),
+                    -1 if end.is_none() => end = Some(v),
+                    _ => return,
+                }
+            }
+            match (start, end) {
+                (None, None) => {
+                    self.start = first;
+                    self.circuit = true;
+                }
+                (Some(s), Some(_)) => self.start = Some(s),
+                _ => (),
+            }
+        } else {
+            // 无向图: 度数为奇数的节点个数为0时存在欧拉回路, 为2时存在欧拉路径
+            // 自环使节点的度数加2
+            let odd: Vec<usize> = (0..n)
+                .filter(|&v| (out_degree[v] + in_degree[v]) % 2 == 1)
+                .collect();
+            match odd.len() {
+                0 => {
+                    self.start = first;
+                    self.circuit = true;
+                }
+                2 => self.start = Some(odd[0]),
+                _ => (),
+            }
+        }
+    }
+
+    // 所有有边的节点必须在同一个连通分量中, 有向图检查弱连通
+    fn is_connected(&self) -> bool {
+        let n = self.graph.v();
+        let mut g = SparseGraph::new(n, false);
+        for &(v, w) in self.edges.iter() {
+            g.add_edge(v, w);
+        }
+
+        let mut component = Component::new(&g);
+        component.count();
+
+        match self.start {
+            Some(s) => (0..n).all(|v| g.adj(v).is_empty() || component.is_connected(s, v)),
+            None => true,
+        }
+    }
+
+    // Hierholzer 算法, 从起点出发不断走未使用的边, 走不下去时回退并记录节点
+    fn hierholzer(&mut self, start: usize) {
+        let n = self.graph.v();
+        let directed = self.graph.is_directed();
+
+        // incident[v] 保存 (相邻节点, 边的编号)
+        let mut incident = vec![vec![]; n];
+        for (id, &(v, w)) in self.edges.iter().enumerate() {
+            incident[v].push((w, id));
+            if !directed && v != w {
+                incident[w].push((v, id));
+            }
+        }
+
+        let mut used = vec![false; self.edges.len()];
+        let mut next = vec![0; n];
+        let mut stack = vec![start];
+
+        while let Some(&v) = stack.last() {
+            // 跳过已经使用过的边
+            while next[v] < incident[v].len() && used[incident[v][next[v]].1] {
+                next[v] += 1;
+            }
+
+            if next[v] == incident[v].len() {
+                self.euler.push(v);
+                stack.pop();
+            } else {
+                let (w, id) = incident[v][next[v]];
+                used[id] = true;
+                stack.push(w);
+            }
+        }
+
+        self.euler.reverse();
+    }
+
+    fn initialize(&mut self) {
+        self.check_degree();
+
+        if !self.is_connected() {
+            self.start = None;
+            self.circuit = false;
+        }
+
+        if let Some(s) = self.start {
+            self.hierholzer(s);
+        }
+        self.initialized = true;
+    }
+
+    // 查询是否存在欧拉路径, 欧拉回路也是欧拉路径
+    pub fn has_path(&mut self) -> bool {
+        if !self.initialized {
+            self.initialize();
+        }
+
+        self.start.is_some() || self.edges.is_empty()
+    }
+
+    // 查询是否存在欧拉回路
+    pub fn has_circuit(&mut self) -> bool {
+        if !self.initialized {
+            self.initialize();
+        }
+
+        self.circuit || self.edges.is_empty()
+    }
+
+    // 查询欧拉路径经过的节点, 存放在vec中
+    // 存在欧拉回路时, 返回的路径起点和终点相同
+    pub fn path(&mut self, vec: &mut Vec<usize>) {
+        assert!(self.has_path());
+
+        vec.extend(self.euler.iter());
+    }
+
+    // 打印出欧拉路径
+    pub fn show_path(&mut self) {
+        assert!(self.has_path());
+
+        let mut vec: Vec<usize> = Vec::new();
+        self.path(&mut vec);
+
+        for (i, v) in vec.iter().enumerate() {
+            print!("{}", v);
+            if i == vec.len() - 1 {
+                println!();
+            } else {
+                print!(" -> ");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DenseGraph;
+
+    // 检查路径恰好使用了图中的每条边一次
+    fn assert_euler<G: Graph>(g: &G, path: &[usize]) {
+        let mut edges = Vec::new();
+        for v in 0..g.v() {
+            for w in g.adj(v) {
+                if g.is_directed() || v <= w {
+                    edges.push((v, w));
+                }
+            }
+        }
+        assert_eq!(path.len(), edges.len() + 1);
+
+        for pair in path.windows(2) {
+            let (v, w) = (pair[0], pair[1]);
+            let pos = edges
+                .iter()
+                .position(|&e| e == (v, w) || (!g.is_directed() && e == (w, v)))
+                .unwrap();
+            edges.swap_remove(pos);
+        }
+        assert!(edges.is_empty());
+    }
+
+    #[test]
+    fn undirected() {
+        // 三角形, 存在欧拉回路
+        let mut g = SparseGraph::new(3, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        let mut euler = Eulerian::new(&g);
+        assert!(euler.has_circuit());
+        let mut path = Vec::new();
+        euler.path(&mut path);
+        assert_eq!(path.first(), path.last());
+        assert_euler(&g, &path);
+
+        // 带对角线的正方形, 只存在欧拉路径
+        let mut g = DenseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        g.add_edge(3, 0);
+        g.add_edge(0, 2);
+        let mut euler = Eulerian::new(&g);
+        assert!(euler.has_path());
+        assert!(!euler.has_circuit());
+        let mut path = Vec::new();
+        euler.path(&mut path);
+        assert_eq!(path[0], 0);
+        assert_eq!(path[path.len() - 1], 2);
+        assert_euler(&g, &path);
+    }
+
+    #[test]
+    fn parallel_edges_and_self_loops() {
+        let mut g = SparseGraph::new(3, false);
+        g.add_edge(0, 1);
+        g.add_edge(0, 1);
+        g.add_edge(1, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 2);
+        let mut euler = Eulerian::new(&g);
+        assert!(euler.has_path());
+        assert!(!euler.has_circuit());
+        let mut path = Vec::new();
+        euler.path(&mut path);
+        assert_euler(&g, &path);
+
+        let mut g = SparseGraph::new(2, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 0);
+        g.add_edge(0, 0);
+        let mut euler = Eulerian::new(&g);
+        assert!(euler.has_circuit());
+        let mut path = Vec::new();
+        euler.path(&mut path);
+        assert_euler(&g, &path);
+    }
+
+    #[test]
+    fn directed() {
+        let mut g = SparseGraph::new(4, true);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(2, 3);
+        let mut euler = Eulerian::new(&g);
+        assert!(euler.has_path());
+        assert!(!euler.has_circuit());
+        let mut path = Vec::new();
+        euler.path(&mut path);
+        assert_eq!(path, vec![2, 0, 1, 2, 3]);
+
+        g.add_edge(3, 2);
+        g.add_edge(1, 1);
+        let mut euler = Eulerian::new(&g);
+        assert!(euler.has_circuit());
+        let mut path = Vec::new();
+        euler.path(&mut path);
+        assert_euler(&g, &path);
+
+        // 节点1的入度比出度大2
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(2, 1);
+        assert!(!Eulerian::new(&g).has_path());
+    }
+
+    #[test]
+    fn not_eulerian() {
+        // 两个不连通的三角形
+        let mut g = SparseGraph::new(6, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(3, 4);
+        g.add_edge(4, 5);
+        g.add_edge(5, 3);
+        let mut euler = Eulerian::new(&g);
+        assert!(!euler.has_path());
+        assert!(!euler.has_circuit());
+
+        // 星型图, 4个奇数度节点
+        let mut g = SparseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(0, 3);
+        assert!(!Eulerian::new(&g).has_path());
+
+        // 没有边的图, 孤立节点不影响结果
+        let g = SparseGraph::new(3, false);
+        let mut euler = Eulerian::new(&g);
+        assert!(euler.has_circuit());
+        let mut path = Vec::new();
+        euler.path(&mut path);
+        assert!(path.is_empty());
+    }
+}
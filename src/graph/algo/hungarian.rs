@@ -0,0 +1,338 @@
+use crate::graph::weight::Weight;
+use crate::graph::WeightedGraph;
+
+// 最小费用完美分配 - Hungarian 算法, O(n^3)
+// cost[i][j] 为 None 表示第i行不能分配给第j列
+pub struct Hungarian<W> {
+    rows: usize,
+    cols: usize,
+    // 补齐成方阵之后的费用矩阵
+    cost: Vec<Vec<Option<W>>>,
+    // assignment[i] 表示第i行分配到的列
+    assignment: Vec<Option<usize>>,
+    total: Option<W>,
+    initialized: bool,
+}
+
+impl<W> Hungarian<W>
+where
+    W: Weight,
+{
+    fn with_cost(rows: usize, cols: usize, cost: Vec<Vec<Option<W>>>) -> Self {
+        // 行数和列数不相等时, 用费用为0的虚拟行或者虚拟列补齐成方阵
+        let n = rows.max(cols);
+        let mut square = vec![vec![Some(W::zero()); n]; n];
+        for (i, row) in cost.into_iter().enumerate() {
+            for (j, c) in row.into_iter().enumerate() {
+                square[i][j] = c;
+            }
+        }
+
+        Self {
+            rows,
+            cols,
+            cost: square,
+            assignment: vec![None; rows],
+            total: None,
+            initialized: false,
+        }
+    }
+
+    // 通过费用矩阵构造, 矩阵可以是长方形
+    pub fn from_matrix(cost: &[Vec<W>]) -> Self {
+        let rows = cost.len();
+        let cols = cost.iter().map(|row| row.len()).max().unwrap_or(0);
+        assert!(cost.iter().all(|row| row.len() == cols));
+
+        let cost = cost
+            .iter()
+            .map(|row| row.iter().map(|&c| Some(c)).collect())
+            .collect();
+        Self::with_cost(rows, cols, cost)
+    }
+
+    // 通过有权二分图构造, 节点 [0, left) 为左侧, [left, v) 为右侧
+    // 边 v-w 的权值即为把v分配给w的费用, 没有边则不能分配
+    pub fn from_graph<G: WeightedGraph<W>>(graph: &G, left: usize) -> Self {
+        assert!(left <= graph.v());
+        let rows = left;
+        let cols = graph.v() - left;
+
+        let mut cost = vec![vec![None; cols]; rows];
+        for (v, row) in cost.iter_mut().enumerate() {
+            for edge in graph.adj(v) {
+                let w = edge.other(v);
+                if w >= left {
+                    row[w - left] = Some(edge.weight());
+                }
+            }
+        }
+        Self::with_cost(rows, cols, cost)
+    }
+
+    // 基于势函数的 Hungarian 算法, 下标从1开始, 第0列为辅助列
+    // 行的势函数 u 只会增加, 列的势函数只会减少, 这里的 v 保存列的势函数的相反数
+    // 这样 u, v 和约化费用 c + v - u 在费用非负时都不会小于0, 无符号整数同样适用
+    // 不存在完美分配时返回 false
+    fn hungarian(&mut self) -> bool {
+        let n = self.cost.len();
+        let zero = W::zero();
+        let mut u = vec![zero; n + 1];
+        let mut v = vec![zero; n + 1];
+        // p[j] 表示第j列分配到的行
+        let mut p = vec![0; n + 1];
+        let mut way = vec![0; n + 1];
+
+        for i in 1..=n {
+            p[0] = i;
+            let mut j0 = 0;
+            let mut minv: Vec<Option<W>> = vec![None; n + 1];
+            let mut used = vec![false; n + 1];
+
+            // 寻找从第i行出发的最短增广路径
+            loop {
+                used[j0] = true;
+                let i0 = p[j0];
+                let mut delta: Option<W> = None;
+                let mut j1 = 0;
+
+                for j in 1..=n {
+                    if used[j] {
+                        continue;
+                    }
+                    if let Some(c) = self.cost[i0 - 1][j - 1] {
+                        let cur = c + v[j] - u[i0];
+                        if minv[j].is_none_or(|m| cur < m) {
+                            minv[j] = Some(cur);
+                            way[j] = j0;
+                        }
+                    }
+                    if let Some(m) = minv[j] {
+                        if delta.is_none_or(|d| m < d) {
+                            delta = Some(m);
+                            j1 = j;
+                        }
+                    }
+                }
+
+                // 所有未访问的列都不可达
+                let delta = match delta {
+                    Some(d) => d,
+                    None => return false,
+                };
+
+                for j in 0..=n {
+                    if used[j] {
+                        u[p[j]] += delta;
+                        v[j] += delta;
+                    } else if let Some(m) = minv[j] {
+                        minv[j] = Some(m - delta);
+                    }
+                }
+
+                j0 = j1;
+                if p[j0] == 0 {
+                    break;
+                }
+            }
+
+            // 沿着增广路径翻转分配关系
+            loop {
+                let j1 = way[j0];
+                p[j0] = p[j1];
+                j0 = j1;
+                if j0 == 0 {
+                    break;
+                }
+            }
+        }
+
+        // 去掉虚拟行和虚拟列上的分配
+        for (j, &i) in p.iter().enumerate().skip(1) {
+            if i >= 1 && i <= self.rows && j <= self.cols {
+                self.assignment[i - 1] = Some(j - 1);
+            }
+        }
+        true
+    }
+
+    fn initialize(&mut self) {
+        if self.hungarian() {
+            let mut total = W::zero();
+            for (i, j) in self.assignment.iter().enumerate() {
+                if let Some(j) = *j {
+                    total += self.cost[i][j].unwrap();
+                }
+            }
+            self.total = Some(total);
+        } else {
+            self.assignment = vec![None; self.rows];
+        }
+        self.initialized = true;
+    }
+
+    // 返回最小总费用, 不存在完美分配时返回 None
+    pub fn result(&mut self) -> Option<W> {
+        if !self.initialized {
+            self.initialize();
+        }
+        self.total
+    }
+
+    // 返回分配方案, 每一对为 (行, 列)
+    // 长方形矩阵中多出来的行或者列不会出现在结果中
+    pub fn assignment(&mut self) -> Vec<(usize, usize)> {
+        if !self.initialized {
+            self.initialize();
+        }
+
+        let mut ret = Vec::new();
+        for (i, j) in self.assignment.iter().enumerate() {
+            if let Some(j) = *j {
+                ret.push((i, j));
+            }
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::weight::OrderedF64;
+    use crate::graph::DenseWeightedGraph;
+    use crate::util::random;
+    use rand::Rng;
+
+    // 枚举所有排列求最小费用
+    fn brute_force(cost: &[Vec<i32>]) -> i32 {
+        fn permute(cost: &[Vec<i32>], row: usize, used: &mut Vec<bool>, sum: i32, best: &mut i32) {
+            if row == cost.len() {
+                *best = (*best).min(sum);
+                return;
+            }
+            for j in 0..cost.len() {
+                if !used[j] {
+                    used[j] = true;
+                    permute(cost, row + 1, used, sum + cost[row][j], best);
+                    used[j] = false;
+                }
+            }
+        }
+
+        let mut best = i32::MAX;
+        permute(cost, 0, &mut vec![false; cost.len()], 0, &mut best);
+        best
+    }
+
+    #[test]
+    fn square() {
+        let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        let mut h = Hungarian::from_matrix(&cost);
+        assert_eq!(h.result(), Some(5));
+        assert_eq!(h.assignment(), vec![(0, 1), (1, 0), (2, 2)]);
+
+        let cost: Vec<Vec<OrderedF64>> = [[1.5, 2.0], [2.5, 1.0]]
+            .iter()
+            .map(|row| row.iter().map(|&c| OrderedF64::new(c)).collect())
+            .collect();
+        let mut h = Hungarian::from_matrix(&cost);
+        assert_eq!(h.result(), Some(OrderedF64::new(2.5)));
+
+        let mut h = Hungarian::<u32>::from_matrix(&[vec![1, 2], vec![2, 1]]);
+        assert_eq!(h.result(), Some(2));
+        assert_eq!(h.assignment(), vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for n in 1..7 {
+                let cost: Vec<Vec<i32>> = (0..n)
+                    .map(|_| (0..n).map(|_| rng.gen_range(-20, 50)).collect())
+                    .collect();
+                let mut h = Hungarian::from_matrix(&cost);
+                assert_eq!(h.result(), Some(brute_force(&cost)));
+                assert_eq!(h.assignment().len(), n);
+
+                // 无符号整数的费用
+                let cost: Vec<Vec<i32>> = (0..n)
+                    .map(|_| (0..n).map(|_| rng.gen_range(0, 50)).collect())
+                    .collect();
+                let expected = brute_force(&cost);
+                let unsigned: Vec<Vec<u32>> = cost
+                    .iter()
+                    .map(|row| row.iter().map(|&c| c as u32).collect())
+                    .collect();
+                assert_eq!(
+                    Hungarian::from_matrix(&unsigned).result(),
+                    Some(expected as u32)
+                );
+                let unsigned: Vec<Vec<usize>> = cost
+                    .iter()
+                    .map(|row| row.iter().map(|&c| c as usize).collect())
+                    .collect();
+                assert_eq!(
+                    Hungarian::from_matrix(&unsigned).result(),
+                    Some(expected as usize)
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn rectangular() {
+        // 2个工人3个任务
+        let cost = vec![vec![9, 2, 7], vec![6, 4, 3]];
+        let mut h = Hungarian::from_matrix(&cost);
+        assert_eq!(h.result(), Some(5));
+        assert_eq!(h.assignment(), vec![(0, 1), (1, 2)]);
+
+        // 3个工人2个任务
+        let cost = vec![vec![9, 6], vec![2, 4], vec![7, 3]];
+        let mut h = Hungarian::from_matrix(&cost);
+        assert_eq!(h.result(), Some(5));
+        assert_eq!(h.assignment(), vec![(1, 0), (2, 1)]);
+
+        let cost: Vec<Vec<i32>> = vec![];
+        let mut h = Hungarian::from_matrix(&cost);
+        assert_eq!(h.result(), Some(0));
+        assert_eq!(h.assignment(), vec![]);
+    }
+
+    #[test]
+    fn dense_weighted_graph() {
+        // 左侧 0,1,2 右侧 3,4,5
+        let mut g: DenseWeightedGraph<i32> = DenseWeightedGraph::new(6, false);
+        let cost = [vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        for (i, row) in cost.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                g.add_edge(i, 3 + j, c);
+            }
+        }
+        let mut h = Hungarian::from_graph(&g, 3);
+        assert_eq!(h.result(), Some(5));
+        assert_eq!(h.assignment(), vec![(0, 1), (1, 0), (2, 2)]);
+
+        let mut g: DenseWeightedGraph<usize> = DenseWeightedGraph::new(6, false);
+        for (i, row) in cost.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                g.add_edge(i, 3 + j, c as usize);
+            }
+        }
+        let mut h = Hungarian::from_graph(&g, 3);
+        assert_eq!(h.result(), Some(5));
+        assert_eq!(h.assignment(), vec![(0, 1), (1, 0), (2, 2)]);
+
+        // 第0行和第1行都只能分配给第0列, 不存在完美分配
+        // 左侧内部的边 0-1 不参与分配
+        let mut g: DenseWeightedGraph<i32> = DenseWeightedGraph::new(4, false);
+        g.add_edge(0, 2, 1);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 1, 1);
+        let mut h = Hungarian::from_graph(&g, 2);
+        assert_eq!(h.result(), None);
+        assert_eq!(h.assignment(), vec![]);
+    }
+}
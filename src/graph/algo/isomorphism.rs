@@ -0,0 +1,600 @@
+use crate::graph::Graph;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+// 图同构和子图同构 - VF2 算法
+// 匹配结果为 mapping, mapping[v] 表示 pattern 中的节点v对应 target 中的节点
+// 平行边视为一条边, 两个图必须同为有向图或者同为无向图
+
+// 匹配方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    // 两个图同构
+    Isomorphism,
+    // pattern 与 target 的某个导出子图同构, 即 target 中对应节点之间不能有多余的边
+    InducedSubgraph,
+    // pattern 中的边在 target 中都存在, target 中可以有多余的边
+    Monomorphism,
+}
+
+// 去重之后的后继和前驱, 均按照从小到大排列, 无向图中两者相同
+struct Adj {
+    succ: Vec<Vec<usize>>,
+    pred: Vec<Vec<usize>>,
+}
+
+impl Adj {
+    fn new<G: Graph>(graph: &G) -> Self {
+        let n = graph.v();
+        let mut succ = vec![vec![]; n];
+        let mut pred = vec![vec![]; n];
+        for (v, list) in succ.iter_mut().enumerate() {
+            for w in graph.adj(v) {
+                list.push(w);
+                pred[w].push(v);
+            }
+        }
+        for list in succ.iter_mut().chain(pred.iter_mut()) {
+            list.sort_unstable();
+            list.dedup();
+        }
+        Self { succ, pred }
+    }
+
+    fn v(&self) -> usize {
+        self.succ.len()
+    }
+
+    // 有向边的条数, 无向图中每条边计算两次, 自环计算一次
+    fn arcs(&self) -> usize {
+        self.succ.iter().map(|list| list.len()).sum()
+    }
+
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.succ[v].binary_search(&w).is_ok()
+    }
+
+    // 按照 (出度, 入度) 从大到小排列的度数序列
+    fn degrees(&self) -> Vec<(usize, usize)> {
+        let mut ret: Vec<(usize, usize)> = (0..self.v())
+            .map(|v| (self.succ[v].len(), self.pred[v].len()))
+            .collect();
+        ret.sort_unstable_by(|a, b| b.cmp(a));
+        ret
+    }
+
+    fn wl_hash(&self) -> u64 {
+        let n = self.v();
+        let hash = |value: &dyn Fn(&mut DefaultHasher)| {
+            let mut hasher = DefaultHasher::new();
+            value(&mut hasher);
+            hasher.finish()
+        };
+
+        // 初始标签为度数和是否有自环
+        let mut labels: Vec<u64> = (0..n)
+            .map(|v| {
+                hash(&|h| (self.succ[v].len(), self.pred[v].len(), self.has_edge(v, v)).hash(h))
+            })
+            .collect();
+        let mut classes = labels.iter().collect::<HashSet<_>>().len();
+
+        // 每一轮用相邻节点标签的多重集合更新标签, 直到划分不再变细
+        // 同构的图在每一轮中的划分个数相同, 因此会在同一轮停止
+        for _ in 0..n {
+            let next: Vec<u64> = (0..n)
+                .map(|v| {
+                    let mut out: Vec<u64> = self.succ[v].iter().map(|&w| labels[w]).collect();
+                    let mut inc: Vec<u64> = self.pred[v].iter().map(|&w| labels[w]).collect();
+                    out.sort_unstable();
+                    inc.sort_unstable();
+                    hash(&|h| (labels[v], &out, &inc).hash(h))
+                })
+                .collect();
+            let next_classes = next.iter().collect::<HashSet<_>>().len();
+            labels = next;
+            if next_classes == classes {
+                break;
+            }
+            classes = next_classes;
+        }
+
+        labels.sort_unstable();
+        hash(&|h| labels.hash(h))
+    }
+}
+
+// 按照 (出度, 入度) 从大到小排列的度数序列, 同构的图度数序列相同
+pub fn degree_sequence<G: Graph>(graph: &G) -> Vec<(usize, usize)> {
+    Adj::new(graph).degrees()
+}
+
+// Weisfeiler-Lehman 哈希, 同构的图哈希值一定相同, 哈希值相同的图不一定同构
+pub fn wl_hash<G: Graph>(graph: &G) -> u64 {
+    Adj::new(graph).wl_hash()
+}
+
+pub struct Vf2<'a, P, T>
+where
+    P: Graph,
+    T: Graph,
+{
+    pattern: &'a P,
+    target: &'a T,
+    mode: Mode,
+}
+
+impl<'a, P, T> Vf2<'a, P, T>
+where
+    P: Graph,
+    T: Graph,
+{
+    pub fn new(pattern: &'a P, target: &'a T, mode: Mode) -> Self {
+        assert_eq!(
+            pattern.is_directed(),
+            target.is_directed(),
+            "pattern and target must be both directed or both undirected"
+        );
+        Self {
+            pattern,
+            target,
+            mode,
+        }
+    }
+
+    // 返回一个匹配, 不存在时返回 None
+    pub fn find(&self) -> Option<Vec<usize>> {
+        self.find_all(1).pop()
+    }
+
+    // 返回最多 limit 个匹配
+    pub fn find_all(&self, limit: usize) -> Vec<Vec<usize>> {
+        let mut ret = Vec::new();
+        if limit == 0 {
+            return ret;
+        }
+
+        let p = Adj::new(self.pattern);
+        let t = Adj::new(self.target);
+        if !prefilter(&p, &t, self.mode) {
+            return ret;
+        }
+
+        let mut state = State::new(p, t, self.mode);
+        state.search(limit, &mut ret);
+        ret
+    }
+}
+
+// 判断两个图是否同构
+pub fn is_isomorphic<A: Graph, B: Graph>(a: &A, b: &B) -> bool {
+    Vf2::new(a, b, Mode::Isomorphism).find().is_some()
+}
+
+// 不需要搜索就能排除的情况
+fn prefilter(p: &Adj, t: &Adj, mode: Mode) -> bool {
+    let (dp, dt) = (p.degrees(), t.degrees());
+    match mode {
+        Mode::Isomorphism => dp == dt && p.wl_hash() == t.wl_hash(),
+        // 度数从大到小排列后, pattern 中第i大的度数不能超过 target 中第i大的度数
+        Mode::InducedSubgraph | Mode::Monomorphism => {
+            p.v() <= t.v()
+                && p.arcs() <= t.arcs()
+                && dp.iter().zip(dt.iter()).all(|(a, b)| a.0 <= b.0)
+                && {
+                    let mut ip: Vec<usize> = dp.iter().map(|d| d.1).collect();
+                    let mut it: Vec<usize> = dt.iter().map(|d| d.1).collect();
+                    ip.sort_unstable_by(|a, b| b.cmp(a));
+                    it.sort_unstable_by(|a, b| b.cmp(a));
+                    ip.iter().zip(it.iter()).all(|(a, b)| a <= b)
+                }
+        }
+    }
+}
+
+// 搜索状态, 下标1表示 pattern, 下标2表示 target
+// out/in 记录节点进入出边/入边终端集合时的深度, 0 表示不在集合中
+// 终端集合为尚未匹配、但与已匹配节点相邻的节点
+struct State {
+    p: Adj,
+    t: Adj,
+    mode: Mode,
+    core1: Vec<Option<usize>>,
+    core2: Vec<Option<usize>>,
+    out1: Vec<usize>,
+    in1: Vec<usize>,
+    out2: Vec<usize>,
+    in2: Vec<usize>,
+    depth: usize,
+}
+
+// 候选节点周围未匹配节点的个数: (在出边终端集合中, 在入边终端集合中, 都不在)
+#[derive(Default)]
+struct Counts {
+    out: usize,
+    inc: usize,
+    new: usize,
+}
+
+impl Counts {
+    // 统计节点v的相邻节点 list 中未匹配的节点, sets 为 (core, out, in)
+    fn new(list: &[usize], v: usize, sets: (&[Option<usize>], &[usize], &[usize])) -> Self {
+        let (core, out, inc) = sets;
+        let mut ret = Self::default();
+        for &w in list.iter().filter(|&&w| w != v && core[w].is_none()) {
+            if out[w] > 0 {
+                ret.out += 1;
+            }
+            if inc[w] > 0 {
+                ret.inc += 1;
+            }
+            if out[w] == 0 && inc[w] == 0 {
+                ret.new += 1;
+            }
+        }
+        ret
+    }
+
+    fn fits(&self, other: &Counts, mode: Mode) -> bool {
+        match mode {
+            Mode::Isomorphism => {
+                self.out == other.out && self.inc == other.inc && self.new == other.new
+            }
+            Mode::InducedSubgraph => {
+                self.out <= other.out && self.inc <= other.inc && self.new <= other.new
+            }
+            // 非导出子图中, pattern 里不在终端集合的节点可能对应 target 终端集合中的节点
+            Mode::Monomorphism => {
+                self.out <= other.out
+                    && self.inc <= other.inc
+                    && self.out + self.inc + self.new <= other.out + other.inc + other.new
+            }
+        }
+    }
+}
+
+impl State {
+    fn new(p: Adj, t: Adj, mode: Mode) -> Self {
+        let (n1, n2) = (p.v(), t.v());
+        Self {
+            p,
+            t,
+            mode,
+            core1: vec![None; n1],
+            core2: vec![None; n2],
+            out1: vec![0; n1],
+            in1: vec![0; n1],
+            out2: vec![0; n2],
+            in2: vec![0; n2],
+            depth: 0,
+        }
+    }
+
+    fn search(&mut self, limit: usize, ret: &mut Vec<Vec<usize>>) {
+        if self.depth == self.p.v() {
+            ret.push(self.core1.iter().map(|m| m.unwrap()).collect());
+            return;
+        }
+
+        let (n, candidates) = self.candidates();
+        for m in candidates {
+            if self.feasible(n, m) {
+                self.push(n, m);
+                self.search(limit, ret);
+                self.pop(n, m);
+                if ret.len() >= limit {
+                    return;
+                }
+            }
+        }
+    }
+
+    // 选择 pattern 中下一个要匹配的节点n, 以及 target 中所有可能与之匹配的节点
+    // n 在出边终端集合中时, 与之匹配的节点也一定在 target 的出边终端集合中, 入边同理
+    fn candidates(&self) -> (usize, Vec<usize>) {
+        let n1 = self.p.v();
+        let n2 = self.t.v();
+        let term1 = |set: &[usize]| (0..n1).find(|&v| set[v] > 0 && self.core1[v].is_none());
+        let term2 = |set: &[usize]| -> Vec<usize> {
+            (0..n2)
+                .filter(|&v| set[v] > 0 && self.core2[v].is_none())
+                .collect()
+        };
+
+        if let Some(n) = term1(&self.out1) {
+            (n, term2(&self.out2))
+        } else if let Some(n) = term1(&self.in1) {
+            (n, term2(&self.in2))
+        } else {
+            let n = (0..n1).find(|&v| self.core1[v].is_none()).unwrap();
+            (n, (0..n2).filter(|&v| self.core2[v].is_none()).collect())
+        }
+    }
+
+    fn feasible(&self, n: usize, m: usize) -> bool {
+        let induced = self.mode != Mode::Monomorphism;
+
+        // 自环
+        let (loop1, loop2) = (self.p.has_edge(n, n), self.t.has_edge(m, m));
+        if loop1 && !loop2 || induced && loop2 && !loop1 {
+            return false;
+        }
+
+        // 已匹配的相邻节点之间的边必须一一对应
+        for &v in self.p.succ[n].iter() {
+            if let Some(w) = self.core1[v] {
+                if !self.t.has_edge(m, w) {
+                    return false;
+                }
+            }
+        }
+        for &v in self.p.pred[n].iter() {
+            if let Some(w) = self.core1[v] {
+                if !self.t.has_edge(w, m) {
+                    return false;
+                }
+            }
+        }
+        if induced {
+            for &w in self.t.succ[m].iter() {
+                if let Some(v) = self.core2[w] {
+                    if !self.p.has_edge(n, v) {
+                        return false;
+                    }
+                }
+            }
+            for &w in self.t.pred[m].iter() {
+                if let Some(v) = self.core2[w] {
+                    if !self.p.has_edge(v, n) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        // 向前看一步, 比较相邻的未匹配节点个数
+        let sets1 = (&self.core1[..], &self.out1[..], &self.in1[..]);
+        let sets2 = (&self.core2[..], &self.out2[..], &self.in2[..]);
+        Counts::new(&self.p.succ[n], n, sets1)
+            .fits(&Counts::new(&self.t.succ[m], m, sets2), self.mode)
+            && Counts::new(&self.p.pred[n], n, sets1)
+                .fits(&Counts::new(&self.t.pred[m], m, sets2), self.mode)
+    }
+
+    fn push(&mut self, n: usize, m: usize) {
+        self.depth += 1;
+        let d = self.depth;
+        self.core1[n] = Some(m);
+        self.core2[m] = Some(n);
+
+        let enter = |adj: &Adj, out: &mut [usize], inc: &mut [usize], v: usize| {
+            for &w in adj.succ[v].iter().chain(std::iter::once(&v)) {
+                if out[w] == 0 {
+                    out[w] = d;
+                }
+            }
+            for &w in adj.pred[v].iter().chain(std::iter::once(&v)) {
+                if inc[w] == 0 {
+                    inc[w] = d;
+                }
+            }
+        };
+        enter(&self.p, &mut self.out1, &mut self.in1, n);
+        enter(&self.t, &mut self.out2, &mut self.in2, m);
+    }
+
+    fn pop(&mut self, n: usize, m: usize) {
+        let d = self.depth;
+        for set in [&mut self.out1, &mut self.in1, &mut self.out2, &mut self.in2] {
+            for x in set.iter_mut().filter(|x| **x == d) {
+                *x = 0;
+            }
+        }
+        self.core1[n] = None;
+        self.core2[m] = None;
+        self.depth -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::algo::transform;
+    use crate::graph::{readgraph, DenseGraph, SparseGraph};
+    use crate::util::random;
+    use rand::{seq::SliceRandom, Rng};
+    use std::path::Path;
+
+    fn cycle(n: usize) -> SparseGraph {
+        let mut g = SparseGraph::new(n, false);
+        for v in 0..n {
+            g.add_edge(v, (v + 1) % n);
+        }
+        g
+    }
+
+    fn complete(n: usize) -> DenseGraph {
+        let mut g = DenseGraph::new(n, false);
+        for v in 0..n {
+            for w in v + 1..n {
+                g.add_edge(v, w);
+            }
+        }
+        g
+    }
+
+    fn random_graph(n: usize, m: usize, directed: bool) -> SparseGraph {
+        let mut rng = random::rng();
+        let mut g = SparseGraph::new(n, directed);
+        for _ in 0..m {
+            g.add_edge(rng.gen_range(0, n), rng.gen_range(0, n));
+        }
+        g
+    }
+
+    // 按照 perm 重新编号, 节点v的新编号为 perm[v]
+    fn relabel(g: &SparseGraph, perm: &[usize]) -> SparseGraph {
+        let mut h = SparseGraph::new(g.v(), g.is_directed());
+        for (v, w) in transform::edges(g) {
+            h.add_edge(perm[v], perm[w]);
+        }
+        h
+    }
+
+    // 检查 mapping 是否为满足 mode 的匹配
+    fn check<P: Graph, T: Graph>(p: &P, t: &T, mapping: &[usize], mode: Mode) -> bool {
+        let distinct: HashSet<&usize> = mapping.iter().collect();
+        if mapping.len() != p.v() || distinct.len() != mapping.len() {
+            return false;
+        }
+        (0..p.v()).all(|v| {
+            (0..p.v()).all(|w| {
+                let (a, b) = (p.has_edge(v, w), t.has_edge(mapping[v], mapping[w]));
+                match mode {
+                    Mode::Monomorphism => !a || b,
+                    _ => a == b,
+                }
+            })
+        })
+    }
+
+    // 暴力枚举所有单射的个数
+    fn brute_force<P: Graph, T: Graph>(p: &P, t: &T, mode: Mode) -> usize {
+        fn go<P: Graph, T: Graph>(p: &P, t: &T, mode: Mode, mapping: &mut Vec<usize>) -> usize {
+            if mapping.len() == p.v() {
+                return check(p, t, mapping, mode) as usize;
+            }
+            let mut total = 0;
+            for w in 0..t.v() {
+                if !mapping.contains(&w) {
+                    mapping.push(w);
+                    total += go(p, t, mode, mapping);
+                    mapping.pop();
+                }
+            }
+            total
+        }
+        go(p, t, mode, &mut vec![])
+    }
+
+    #[test]
+    fn isomorphism() {
+        random::reproducible(|| {
+            let filename = Path::new("./src/files/graph/test1.txt");
+            let mut g = SparseGraph::new(13, false);
+            readgraph::read(&mut g, filename).unwrap();
+
+            let mut perm: Vec<usize> = (0..13).collect();
+            perm.shuffle(&mut random::rng());
+            let h = relabel(&g, &perm);
+            assert_eq!(degree_sequence(&g), degree_sequence(&h));
+            assert_eq!(wl_hash(&g), wl_hash(&h));
+
+            let mapping = Vf2::new(&g, &h, Mode::Isomorphism).find().unwrap();
+            assert!(check(&g, &h, &mapping, Mode::Isomorphism));
+
+            // 少一条边
+            let mut h2 = SparseGraph::new(13, false);
+            for (v, w) in transform::edges(&h).into_iter().skip(1) {
+                h2.add_edge(v, w);
+            }
+            assert!(!is_isomorphic(&g, &h2));
+        });
+    }
+
+    #[test]
+    fn same_wl_hash() {
+        // 6个节点的环和两个三角形, 度数序列和 WL 哈希都相同, 但是不同构
+        let c6 = cycle(6);
+        let two_triangles: SparseGraph = transform::disjoint_union(&cycle(3), &cycle(3));
+        assert_eq!(degree_sequence(&c6), degree_sequence(&two_triangles));
+        assert_eq!(wl_hash(&c6), wl_hash(&two_triangles));
+        assert!(!is_isomorphic(&c6, &two_triangles));
+        assert!(is_isomorphic(&c6, &c6));
+        assert_ne!(wl_hash(&c6), wl_hash(&cycle(5)));
+    }
+
+    #[test]
+    fn subgraph() {
+        let k4 = complete(4);
+        let c4 = cycle(4);
+        let triangle = cycle(3);
+        let mut path = SparseGraph::new(3, false);
+        path.add_edge(0, 1);
+        path.add_edge(1, 2);
+
+        // K4 中的三角形: 4 种节点组合 * 6 个自同构
+        assert_eq!(
+            Vf2::new(&triangle, &k4, Mode::InducedSubgraph)
+                .find_all(100)
+                .len(),
+            24
+        );
+        // C4 中的长度为2的路径
+        assert_eq!(
+            Vf2::new(&path, &c4, Mode::InducedSubgraph)
+                .find_all(100)
+                .len(),
+            8
+        );
+        // K4 中任意三个节点之间都有三条边, 所以没有导出的路径, 但是有非导出的路径
+        assert!(Vf2::new(&path, &k4, Mode::InducedSubgraph).find().is_none());
+        assert_eq!(
+            Vf2::new(&path, &k4, Mode::Monomorphism).find_all(100).len(),
+            24
+        );
+        assert_eq!(
+            Vf2::new(&path, &k4, Mode::Monomorphism).find_all(5).len(),
+            5
+        );
+        assert!(Vf2::new(&triangle, &c4, Mode::Monomorphism)
+            .find()
+            .is_none());
+    }
+
+    #[test]
+    fn directed() {
+        let mut a = SparseGraph::new(3, true);
+        a.add_edge(0, 1);
+        a.add_edge(1, 2);
+        let mut b = SparseGraph::new(3, true);
+        b.add_edge(2, 1);
+        b.add_edge(1, 0);
+        let mut c = SparseGraph::new(3, true);
+        c.add_edge(0, 1);
+        c.add_edge(2, 1);
+
+        let mapping = Vf2::new(&a, &b, Mode::Isomorphism).find().unwrap();
+        assert_eq!(mapping, vec![2, 1, 0]);
+        assert!(!is_isomorphic(&a, &c));
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for _ in 0..100 {
+                let directed = rng.gen();
+                let n1 = rng.gen_range(1, 5);
+                let n2 = rng.gen_range(n1, 7);
+                let p = random_graph(n1, rng.gen_range(0, 2 * n1), directed);
+                let t = random_graph(n2, rng.gen_range(0, 3 * n2), directed);
+
+                for mode in [Mode::InducedSubgraph, Mode::Monomorphism] {
+                    let all = Vf2::new(&p, &t, mode).find_all(usize::MAX);
+                    assert_eq!(all.len(), brute_force(&p, &t, mode));
+                    assert!(all.iter().all(|m| check(&p, &t, m, mode)));
+                }
+
+                let t = random_graph(n1, rng.gen_range(0, 2 * n1), directed);
+                assert_eq!(
+                    Vf2::new(&p, &t, Mode::Isomorphism)
+                        .find_all(usize::MAX)
+                        .len(),
+                    brute_force(&p, &t, Mode::Isomorphism)
+                );
+            }
+        });
+    }
+}
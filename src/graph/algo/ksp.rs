@@ -0,0 +1,356 @@
+use crate::graph::weight::Weight;
+use crate::graph::{Graph, WeightedGraph};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+
+// 前k条最短的简单路径 - Yen 算法
+// 每条路径以 (路径上的节点, 路径长度) 的形式返回, 按照长度从小到大排列
+
+// 邻接表, adj[v] 中为 (w, 权值), 平行边只保留权值最小的一条
+type Adj<W> = Vec<Vec<(usize, W)>>;
+
+// 计算最短路径时需要避开的节点和边
+struct Blocked {
+    vertices: Vec<bool>,
+    edges: HashSet<(usize, usize)>,
+}
+
+impl Blocked {
+    fn new(n: usize) -> Self {
+        Self {
+            vertices: vec![false; n],
+            edges: HashSet::new(),
+        }
+    }
+
+    fn allows(&self, v: usize, w: usize) -> bool {
+        !self.vertices[w] && !self.edges.contains(&(v, w))
+    }
+}
+
+fn build_adj<W: Weight>(n: usize, arcs: impl Iterator<Item = (usize, usize, W)>) -> Adj<W> {
+    let mut adj: Adj<W> = vec![vec![]; n];
+    for (v, w, weight) in arcs {
+        match adj[v].iter_mut().find(|(x, _)| *x == w) {
+            Some((_, old)) if weight < *old => *old = weight,
+            Some(_) => (),
+            None => adj[v].push((w, weight)),
+        }
+    }
+    adj
+}
+
+fn edge_weight<W: Weight>(adj: &Adj<W>, v: usize, w: usize) -> W {
+    adj[v].iter().find(|(x, _)| *x == w).unwrap().1
+}
+
+// 通过 from 数组还原从s到t的路径
+fn trace(from: &[Option<usize>], s: usize, t: usize) -> Vec<usize> {
+    let mut path = vec![t];
+    let mut v = t;
+    while v != s {
+        v = from[v].unwrap();
+        path.push(v);
+    }
+    path.reverse();
+    path
+}
+
+fn dijkstra<W: Weight>(
+    adj: &Adj<W>,
+    s: usize,
+    t: usize,
+    blocked: &Blocked,
+) -> Option<(Vec<usize>, W)> {
+    let n = adj.len();
+    let mut dist: Vec<Option<W>> = vec![None; n];
+    let mut from = vec![None; n];
+    let mut marked = vec![false; n];
+    let mut pq = BinaryHeap::new();
+    dist[s] = Some(W::zero());
+    pq.push(Reverse((W::zero(), s)));
+
+    while let Some(Reverse((d, v))) = pq.pop() {
+        if marked[v] {
+            continue;
+        }
+        if v == t {
+            return Some((trace(&from, s, t), d));
+        }
+        marked[v] = true;
+
+        for &(w, weight) in adj[v].iter() {
+            if !blocked.allows(v, w) || marked[w] {
+                continue;
+            }
+            let alt = d + weight;
+            if dist[w].is_none_or(|old| alt < old) {
+                dist[w] = Some(alt);
+                from[w] = Some(v);
+                pq.push(Reverse((alt, w)));
+            }
+        }
+    }
+    None
+}
+
+fn bfs(adj: &Adj<usize>, s: usize, t: usize, blocked: &Blocked) -> Option<(Vec<usize>, usize)> {
+    let n = adj.len();
+    let mut order: Vec<Option<usize>> = vec![None; n];
+    let mut from = vec![None; n];
+    let mut queue = VecDeque::new();
+    order[s] = Some(0);
+    queue.push_back(s);
+
+    while let Some(v) = queue.pop_front() {
+        if v == t {
+            return Some((trace(&from, s, t), order[t].unwrap()));
+        }
+        for &(w, _) in adj[v].iter() {
+            if blocked.allows(v, w) && order[w].is_none() {
+                order[w] = Some(order[v].unwrap() + 1);
+                from[w] = Some(v);
+                queue.push_back(w);
+            }
+        }
+    }
+    None
+}
+
+fn yen<W, F>(adj: &Adj<W>, s: usize, t: usize, k: usize, shortest: F) -> Vec<(Vec<usize>, W)>
+where
+    W: Weight,
+    F: Fn(&Adj<W>, usize, usize, &Blocked) -> Option<(Vec<usize>, W)>,
+{
+    let n = adj.len();
+    assert!(s < n && t < n);
+
+    let mut ret: Vec<(Vec<usize>, W)> = Vec::new();
+    if k == 0 {
+        return ret;
+    }
+    match shortest(adj, s, t, &Blocked::new(n)) {
+        Some(first) => ret.push(first),
+        None => return ret,
+    }
+
+    // 候选路径, 按照长度从小到大取出, seen 用于去掉重复的候选路径
+    let mut candidates = BinaryHeap::new();
+    let mut seen: HashSet<Vec<usize>> = HashSet::new();
+    seen.insert(ret[0].0.clone());
+
+    while ret.len() < k {
+        let last = ret.last().unwrap().0.clone();
+
+        // 以上一条路径中的每个节点作为偏离点
+        let mut root_cost = W::zero();
+        for i in 0..last.len() - 1 {
+            let spur = last[i];
+            let root = &last[..=i];
+
+            // 与当前路径有相同前缀的路径, 禁止从偏离点走它们的下一条边
+            let mut blocked = Blocked::new(n);
+            for (path, _) in ret.iter() {
+                if path.len() > i + 1 && &path[..=i] == root {
+                    blocked.edges.insert((path[i], path[i + 1]));
+                }
+            }
+            // 前缀中的节点不能再次出现, 保证路径是简单路径
+            for &v in root[..i].iter() {
+                blocked.vertices[v] = true;
+            }
+
+            if let Some((spur_path, spur_cost)) = shortest(adj, spur, t, &blocked) {
+                let mut path = root[..i].to_vec();
+                path.extend(spur_path);
+                if seen.insert(path.clone()) {
+                    candidates.push(Reverse((root_cost + spur_cost, path)));
+                }
+            }
+
+            root_cost += edge_weight(adj, spur, last[i + 1]);
+        }
+
+        match candidates.pop() {
+            Some(Reverse((cost, path))) => ret.push((path, cost)),
+            None => break,
+        }
+    }
+    ret
+}
+
+// 有权图中从s到t的前k条最短简单路径, 边的权值需要非负
+pub fn k_shortest_paths<W, G>(graph: &G, s: usize, t: usize, k: usize) -> Vec<(Vec<usize>, W)>
+where
+    W: Weight,
+    G: WeightedGraph<W>,
+{
+    let adj = build_adj(
+        graph.v(),
+        (0..graph.v()).flat_map(|v| {
+            graph.adj(v).into_iter().map(move |e| {
+                assert!(e.weight() >= W::zero(), "negative weight {}", e.weight());
+                (v, e.other(v), e.weight())
+            })
+        }),
+    );
+    yen(&adj, s, t, k, dijkstra)
+}
+
+// 无权图中从s到t的前k条最短简单路径, 路径长度为边的条数
+pub fn k_shortest_paths_unweighted<G: Graph>(
+    graph: &G,
+    s: usize,
+    t: usize,
+    k: usize,
+) -> Vec<(Vec<usize>, usize)> {
+    let adj = build_adj(
+        graph.v(),
+        (0..graph.v()).flat_map(|v| graph.adj(v).into_iter().map(move |w| (v, w, 1))),
+    );
+    yen(&adj, s, t, k, bfs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::algo::path::ShortestPath;
+    use crate::graph::weight::OrderedF64;
+    use crate::graph::{readgraph, DenseWeightedGraph, SparseGraph, SparseWeightedGraph};
+    use crate::util::random;
+    use rand::Rng;
+    use std::path::Path;
+
+    // 枚举所有简单路径
+    fn all_paths(
+        adj: &Adj<u32>,
+        v: usize,
+        t: usize,
+        path: &mut Vec<usize>,
+        cost: u32,
+        out: &mut Vec<u32>,
+    ) {
+        if v == t {
+            out.push(cost);
+            return;
+        }
+        for &(w, weight) in adj[v].iter() {
+            if !path.contains(&w) {
+                path.push(w);
+                all_paths(adj, w, t, path, cost + weight, out);
+                path.pop();
+            }
+        }
+    }
+
+    fn is_simple_path<W: Weight>(adj: &Adj<W>, path: &[usize], s: usize, t: usize) -> bool {
+        let distinct: HashSet<&usize> = path.iter().collect();
+        path[0] == s
+            && *path.last().unwrap() == t
+            && distinct.len() == path.len()
+            && path
+                .windows(2)
+                .all(|e| adj[e[0]].iter().any(|&(w, _)| w == e[1]))
+    }
+
+    #[test]
+    fn yen_example() {
+        // Yen 算法常用的例子, C=0 D=1 E=2 F=3 G=4 H=5
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(6, true);
+        g.add_edge(0, 1, 3);
+        g.add_edge(0, 2, 2);
+        g.add_edge(1, 3, 4);
+        g.add_edge(2, 1, 1);
+        g.add_edge(2, 3, 2);
+        g.add_edge(2, 4, 3);
+        g.add_edge(3, 4, 2);
+        g.add_edge(3, 5, 1);
+        g.add_edge(4, 5, 2);
+
+        let paths = k_shortest_paths(&g, 0, 5, 3);
+        assert_eq!(
+            paths,
+            vec![
+                (vec![0, 2, 3, 5], 5),
+                (vec![0, 2, 4, 5], 7),
+                (vec![0, 1, 3, 5], 8),
+            ]
+        );
+
+        // 路径不足k条时返回所有路径
+        assert_eq!(k_shortest_paths(&g, 0, 5, 100).len(), 7);
+        assert_eq!(k_shortest_paths(&g, 5, 0, 3), vec![]);
+        assert_eq!(k_shortest_paths(&g, 2, 2, 3), vec![(vec![2], 0)]);
+        assert_eq!(k_shortest_paths(&g, 0, 5, 0), vec![]);
+    }
+
+    #[test]
+    fn float_weights() {
+        let filename = Path::new("./src/files/graph/test3.txt");
+        let mut g: SparseWeightedGraph<OrderedF64> = SparseWeightedGraph::new(8, false);
+        readgraph::read_weighted_graph(&mut g, filename).unwrap();
+
+        let paths = k_shortest_paths(&g, 0, 6, 4);
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths[0].0, vec![0, 6]);
+        assert_eq!(paths[1].0, vec![0, 2, 6]);
+        for pair in paths.windows(2) {
+            assert!(pair[0].1 <= pair[1].1);
+        }
+    }
+
+    #[test]
+    fn unweighted() {
+        let filename = Path::new("./src/files/graph/test2.txt");
+        let mut g = SparseGraph::new(7, false);
+        readgraph::read(&mut g, filename).unwrap();
+
+        let paths = k_shortest_paths_unweighted(&g, 0, 3, 5);
+        let mut bfs = ShortestPath::new(&g, 0);
+        assert_eq!(Some(paths[0].1), bfs.length(3));
+        for pair in paths.windows(2) {
+            assert!(pair[0].1 <= pair[1].1);
+        }
+        for (path, len) in paths.iter() {
+            assert_eq!(path.len() - 1, *len);
+        }
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for _ in 0..30 {
+                let n = rng.gen_range(2, 8);
+                let mut g: SparseWeightedGraph<u32> = SparseWeightedGraph::new(n, rng.gen());
+                for _ in 0..rng.gen_range(0, 3 * n) {
+                    g.add_edge(
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, 10),
+                    );
+                }
+                let adj = build_adj(
+                    n,
+                    (0..n).flat_map(|v| {
+                        g.adj(v)
+                            .into_iter()
+                            .map(move |e| (v, e.other(v), e.weight()))
+                    }),
+                );
+
+                let mut expected = Vec::new();
+                all_paths(&adj, 0, n - 1, &mut vec![0], 0, &mut expected);
+                expected.sort_unstable();
+                expected.truncate(5);
+
+                let paths = k_shortest_paths(&g, 0, n - 1, 5);
+                let costs: Vec<u32> = paths.iter().map(|p| p.1).collect();
+                assert_eq!(costs, expected);
+                for (path, _) in paths.iter() {
+                    assert!(is_simple_path(&adj, path, 0, n - 1));
+                }
+            }
+        });
+    }
+}
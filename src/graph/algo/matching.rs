@@ -0,0 +1,274 @@
+use crate::graph::Graph;
+use std::collections::VecDeque;
+use std::fmt;
+
+const INF: usize = usize::MAX;
+
+#[derive(Debug, PartialEq)]
+pub enum MatchingError {
+    // 图中存在奇数环, 不是二分图, 记录下发现冲突的那条边
+    NotBipartite(usize, usize),
+}
+
+impl fmt::Display for MatchingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatchingError::NotBipartite(v, w) => {
+                write!(f, "graph is not bipartite, conflict edge {}-{}", v, w)
+            }
+        }
+    }
+}
+
+// 二分图最大匹配 - Hopcroft-Karp 算法, O(E * sqrt(V))
+pub struct HopcroftKarp {
+    // 无向化之后的邻接表, 有向边也当作无向边处理
+    adj: Vec<Vec<usize>>,
+    // left[v] 为 true 表示 v 在二分图的左侧
+    left: Vec<bool>,
+    // mate[v] 表示和 v 匹配的节点
+    mate: Vec<Option<usize>>,
+    // 左侧节点在分层图中的层数
+    dist: Vec<usize>,
+    initialized: bool,
+}
+
+impl HopcroftKarp {
+    pub fn new<G: Graph>(graph: &G) -> Result<Self, MatchingError> {
+        let n = graph.v();
+        let mut adj = vec![vec![]; n];
+        for v in 0..n {
+            for w in graph.adj(v) {
+                adj[v].push(w);
+                if v != w {
+                    adj[w].push(v);
+                }
+            }
+        }
+
+        // 广度优先遍历对节点二染色, 相邻节点颜色相同则不是二分图
+        let mut color: Vec<Option<bool>> = vec![None; n];
+        for s in 0..n {
+            if color[s].is_some() {
+                continue;
+            }
+            color[s] = Some(true);
+            let mut queue = VecDeque::new();
+            queue.push_back(s);
+            while let Some(v) = queue.pop_front() {
+                for &w in adj[v].iter() {
+                    match color[w] {
+                        None => {
+                            color[w] = color[v].map(|c| !c);
+                            queue.push_back(w);
+                        }
+                        Some(c) if Some(c) == color[v] => {
+                            return Err(MatchingError::NotBipartite(v, w));
+                        }
+                        Some(_) => (),
+                    }
+                }
+            }
+        }
+
+        let left = color.into_iter().map(|c| c.unwrap()).collect();
+
+        Ok(Self {
+            adj,
+            left,
+            mate: vec![None; n],
+            dist: vec![INF; n],
+            initialized: false,
+        })
+    }
+
+    // 从所有未匹配的左侧节点出发构建分层图
+    // 返回是否存在增广路径
+    fn bfs(&mut self) -> bool {
+        let mut queue = VecDeque::new();
+        for u in 0..self.adj.len() {
+            if self.left[u] && self.mate[u].is_none() {
+                self.dist[u] = 0;
+                queue.push_back(u);
+            } else {
+                self.dist[u] = INF;
+            }
+        }
+
+        let mut found = false;
+        while let Some(u) = queue.pop_front() {
+            for &v in self.adj[u].iter() {
+                match self.mate[v] {
+                    None => found = true,
+                    Some(next) => {
+                        if self.dist[next] == INF {
+                            self.dist[next] = self.dist[u] + 1;
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    // 沿着分层图寻找从u出发的增广路径
+    fn dfs(&mut self, u: usize) -> bool {
+        for i in 0..self.adj[u].len() {
+            let v = self.adj[u][i];
+            let augment = match self.mate[v] {
+                None => true,
+                Some(next) => self.dist[next] == self.dist[u] + 1 && self.dfs(next),
+            };
+            if augment {
+                self.mate[u] = Some(v);
+                self.mate[v] = Some(u);
+                return true;
+            }
+        }
+
+        // 从u出发找不到增广路径, 本轮不再访问u
+        self.dist[u] = INF;
+        false
+    }
+
+    fn initialize(&mut self) {
+        while self.bfs() {
+            for u in 0..self.adj.len() {
+                if self.left[u] && self.mate[u].is_none() {
+                    self.dfs(u);
+                }
+            }
+        }
+        self.initialized = true;
+    }
+
+    // 返回匹配的边, 每一对为 (左侧节点, 右侧节点)
+    pub fn matching(&mut self) -> Vec<(usize, usize)> {
+        if !self.initialized {
+            self.initialize();
+        }
+
+        let mut ret = Vec::new();
+        for u in 0..self.adj.len() {
+            if self.left[u] {
+                if let Some(v) = self.mate[u] {
+                    ret.push((u, v));
+                }
+            }
+        }
+        ret
+    }
+
+    // 返回最大匹配的边数
+    pub fn size(&mut self) -> usize {
+        self.matching().len()
+    }
+
+    // 根据 König 定理由最大匹配构造最小点覆盖
+    // 从未匹配的左侧节点出发走交错路径, 访问到的节点集合为Z
+    // 最小点覆盖为 (左侧 - Z) + (右侧 ∩ Z)
+    pub fn vertex_cover(&mut self) -> Vec<usize> {
+        if !self.initialized {
+            self.initialize();
+        }
+
+        let n = self.adj.len();
+        let mut visited: Vec<bool> = (0..n)
+            .map(|u| self.left[u] && self.mate[u].is_none())
+            .collect();
+        let mut queue: VecDeque<usize> = (0..n).filter(|&u| visited[u]).collect();
+
+        while let Some(u) = queue.pop_front() {
+            // 左侧节点走非匹配边, 右侧节点走匹配边
+            for &v in self.adj[u].iter() {
+                let alternating = if self.left[u] {
+                    self.mate[u] != Some(v)
+                } else {
+                    self.mate[u] == Some(v)
+                };
+                if alternating && !visited[v] {
+                    visited[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+
+        (0..n).filter(|&v| self.left[v] != visited[v]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{readgraph, DenseGraph, SparseGraph};
+    use std::path::Path;
+
+    fn assert_cover<G: Graph>(g: &G, cover: &[usize]) {
+        for v in 0..g.v() {
+            for w in g.adj(v) {
+                assert!(cover.contains(&v) || cover.contains(&w));
+            }
+        }
+    }
+
+    #[test]
+    fn maximum_matching() {
+        // 左侧 0,1,2,3 右侧 4,5,6,7
+        let mut g = SparseGraph::new(8, false);
+        g.add_edge(0, 4);
+        g.add_edge(0, 5);
+        g.add_edge(1, 4);
+        g.add_edge(2, 5);
+        g.add_edge(2, 6);
+        g.add_edge(3, 6);
+        g.add_edge(3, 7);
+
+        let mut hk = HopcroftKarp::new(&g).unwrap();
+        assert_eq!(hk.size(), 4);
+        for (u, v) in hk.matching() {
+            assert!(g.has_edge(u, v));
+        }
+
+        let cover = hk.vertex_cover();
+        assert_eq!(cover.len(), 4);
+        assert_cover(&g, &cover);
+    }
+
+    #[test]
+    fn konig_cover() {
+        // 星型图, 中心一个节点即可覆盖所有边
+        let mut g = DenseGraph::new(5, false);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(0, 3);
+        g.add_edge(0, 4);
+
+        let mut hk = HopcroftKarp::new(&g).unwrap();
+        assert_eq!(hk.size(), 1);
+        assert_eq!(hk.vertex_cover(), vec![0]);
+
+        // 没有边的图
+        let g = SparseGraph::new(3, false);
+        let mut hk = HopcroftKarp::new(&g).unwrap();
+        assert_eq!(hk.matching(), vec![]);
+        assert_eq!(hk.vertex_cover(), vec![]);
+    }
+
+    #[test]
+    fn not_bipartite() {
+        // test2.txt 中 3-4-5 构成三角形
+        let filename = Path::new("./src/files/graph/test2.txt");
+        let mut g = SparseGraph::new(7, false);
+        readgraph::read(&mut g, filename).unwrap();
+        assert!(HopcroftKarp::new(&g).is_err());
+
+        // 自环
+        let mut g = SparseGraph::new(2, false);
+        g.add_edge(1, 1);
+        assert_eq!(
+            HopcroftKarp::new(&g).err(),
+            Some(MatchingError::NotBipartite(1, 1))
+        );
+    }
+}
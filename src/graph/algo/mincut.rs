@@ -0,0 +1,229 @@
+use crate::graph::weight::Weight;
+use crate::graph::WeightedGraph;
+
+// 无向有权图的全局最小割 - Stoer-Wagner 算法, O(V^3)
+pub struct StoerWagner<W> {
+    // 邻接矩阵, 平行边的权值累加, 忽略自环
+    g: Vec<Vec<W>>,
+    cut_weight: Option<W>,
+    // 最小割中包含的一侧节点
+    cut: Vec<usize>,
+    initialized: bool,
+}
+
+impl<W> StoerWagner<W>
+where
+    W: Weight,
+{
+    // 稠密图和稀疏图都先转换为邻接矩阵
+    pub fn new<G: WeightedGraph<W>>(graph: &G) -> Self {
+        assert!(!graph.is_directed());
+
+        let n = graph.v();
+        let mut g = vec![vec![W::zero(); n]; n];
+        for v in 0..n {
+            for edge in graph.adj(v) {
+                let (a, b) = (v, edge.other(v));
+                // 无向图中每条边在两个端点各出现一次, 只累加一次
+                if a < b {
+                    g[a][b] += edge.weight();
+                    g[b][a] += edge.weight();
+                }
+            }
+        }
+
+        Self {
+            g,
+            cut_weight: None,
+            cut: Vec::new(),
+            initialized: false,
+        }
+    }
+
+    fn stoer_wagner(&mut self) {
+        let n = self.g.len();
+        // groups[v] 表示合并到v中的所有原始节点
+        let mut groups: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+        let mut merged = vec![false; n];
+
+        for phase in 1..n {
+            // 按照最大邻接顺序依次加入节点
+            // weights[v] 表示v与已加入集合之间边的权值和
+            let mut weights = vec![W::zero(); n];
+            let mut added = vec![false; n];
+            let mut prev = 0;
+            let remain = n - phase + 1;
+
+            for i in 0..remain {
+                let mut sel = None;
+                for v in 0..n {
+                    if !merged[v] && !added[v] && sel.is_none_or(|s| weights[v] > weights[s]) {
+                        sel = Some(v);
+                    }
+                }
+                let sel = sel.unwrap();
+                added[sel] = true;
+
+                if i == remain - 1 {
+                    // 最后加入的节点和其余节点之间的割即为本阶段的割
+                    if self.cut_weight.is_none_or(|c| weights[sel] < c) {
+                        self.cut_weight = Some(weights[sel]);
+                        self.cut = groups[sel].clone();
+                    }
+
+                    // 将最后两个加入的节点合并
+                    let group = std::mem::take(&mut groups[sel]);
+                    groups[prev].extend(group);
+                    for v in 0..n {
+                        self.g[prev][v] = self.g[prev][v] + self.g[sel][v];
+                        self.g[v][prev] = self.g[prev][v];
+                    }
+                    merged[sel] = true;
+                } else {
+                    prev = sel;
+                    for v in 0..n {
+                        if !merged[v] && !added[v] {
+                            weights[v] += self.g[sel][v];
+                        }
+                    }
+                }
+            }
+        }
+
+        self.cut.sort_unstable();
+    }
+
+    fn initialize(&mut self) {
+        self.stoer_wagner();
+        self.initialized = true;
+    }
+
+    // 返回最小割的权值, 节点数小于2时不存在割
+    pub fn result(&mut self) -> Option<W> {
+        if !self.initialized {
+            self.initialize();
+        }
+        self.cut_weight
+    }
+
+    // 返回最小割划分出的两部分节点
+    pub fn partition(&mut self) -> (Vec<usize>, Vec<usize>) {
+        if !self.initialized {
+            self.initialize();
+        }
+
+        let other = (0..self.g.len())
+            .filter(|v| !self.cut.contains(v))
+            .collect();
+        (self.cut.clone(), other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::weight::OrderedF64;
+    use crate::graph::{readgraph, DenseWeightedGraph, SparseWeightedGraph};
+    use crate::util::random;
+    use rand::Rng;
+    use std::path::Path;
+
+    fn cut_weight<G: WeightedGraph<u32>>(g: &G, side: &[usize]) -> u32 {
+        let mut weight = 0;
+        for &v in side.iter() {
+            for edge in g.adj(v) {
+                if !side.contains(&edge.other(v)) {
+                    weight += edge.weight();
+                }
+            }
+        }
+        weight
+    }
+
+    // 枚举所有包含节点0的真子集
+    fn brute_force<G: WeightedGraph<u32>>(g: &G) -> u32 {
+        let n = g.v();
+        let mut best = u32::MAX;
+        for mask in 0..(1u32 << (n - 1)) - 1 {
+            let side: Vec<usize> = (0..n)
+                .filter(|&v| v == 0 || mask & (1 << (v - 1)) != 0)
+                .collect();
+            best = best.min(cut_weight(g, &side));
+        }
+        best
+    }
+
+    #[test]
+    fn min_cut() {
+        // 两个三角形由一条权值为1的边相连
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(6, false);
+        g.add_edge(0, 1, 3);
+        g.add_edge(1, 2, 3);
+        g.add_edge(2, 0, 3);
+        g.add_edge(3, 4, 3);
+        g.add_edge(4, 5, 3);
+        g.add_edge(5, 3, 3);
+        g.add_edge(2, 3, 1);
+
+        let mut sw = StoerWagner::new(&g);
+        assert_eq!(sw.result(), Some(1));
+        let (a, b) = sw.partition();
+        assert!(a == vec![0, 1, 2] || a == vec![3, 4, 5]);
+        assert_eq!(a.len() + b.len(), 6);
+
+        // 不连通的图最小割为0
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(3, false);
+        g.add_edge(0, 1, 5);
+        assert_eq!(StoerWagner::new(&g).result(), Some(0));
+
+        // 只有一个节点时不存在割
+        let g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(1, false);
+        assert_eq!(StoerWagner::new(&g).result(), None);
+    }
+
+    #[test]
+    fn sparse_weighted_graph() {
+        // 平行边的权值累加
+        let mut g: SparseWeightedGraph<u32> = SparseWeightedGraph::new(3, false);
+        g.add_edge(0, 1, 2);
+        g.add_edge(0, 1, 2);
+        g.add_edge(1, 2, 3);
+        g.add_edge(2, 2, 7);
+        let mut sw = StoerWagner::new(&g);
+        assert_eq!(sw.result(), Some(3));
+        let (a, b) = sw.partition();
+        assert!(a == vec![2] || b == vec![2]);
+
+        let filename = Path::new("./src/files/graph/test3.txt");
+        let mut g: SparseWeightedGraph<OrderedF64> = SparseWeightedGraph::new(8, false);
+        readgraph::read_weighted_graph(&mut g, filename).unwrap();
+        let mut sw = StoerWagner::new(&g);
+        let weight = sw.result().unwrap();
+        assert!((weight.value() - 0.95).abs() < 1e-9);
+        assert_eq!(sw.partition().0, vec![5]);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for _ in 0..50 {
+                let n = rng.gen_range(2, 9);
+                let mut g: SparseWeightedGraph<u32> = SparseWeightedGraph::new(n, false);
+                for _ in 0..rng.gen_range(0, 2 * n) {
+                    g.add_edge(
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, n),
+                        rng.gen_range(1, 10),
+                    );
+                }
+
+                let mut sw = StoerWagner::new(&g);
+                let expected = brute_force(&g);
+                assert_eq!(sw.result(), Some(expected));
+                let (a, _) = sw.partition();
+                assert_eq!(cut_weight(&g, &a), expected);
+            }
+        });
+    }
+}
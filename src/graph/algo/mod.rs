@@ -1,3 +1,17 @@
+pub mod centrality;
+pub mod coloring;
 pub mod components;
+pub mod connectivity;
+pub mod dial;
+pub mod dijkstra;
+pub mod euler;
+pub mod hungarian;
+pub mod isomorphism;
+pub mod ksp;
+pub mod matching;
+pub mod mincut;
 pub mod path;
 pub mod prim;
+pub mod stats;
+pub mod transform;
+pub mod zero_one;
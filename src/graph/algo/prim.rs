@@ -1,52 +1,9 @@
+use crate::graph::edge::ByWeight;
+use crate::graph::weight::Weight;
 use crate::graph::{Edge, WeightedGraph};
-use std::cmp::{Ord, Ordering, Reverse};
+use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::collections::VecDeque;
-use std::fmt::{self, Display};
-use std::ops::AddAssign;
-use std::str::FromStr;
-
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Float(pub f64);
-
-impl Eq for Float {}
-
-impl PartialOrd for Float {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.0.partial_cmp(&other.0)
-    }
-}
-
-impl Ord for Float {
-    fn cmp(&self, other: &Float) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-impl Display for Float {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
-    }
-}
-
-impl AddAssign for Float {
-    fn add_assign(&mut self, other: Self) {
-        // *self = Self {
-        //     x: self.x + other.x,
-        //     y: self.y + other.y,
-        // };
-        self.0.add_assign(other.0);
-    }
-}
-
-impl FromStr for Float {
-    type Err = std::num::ParseFloatError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let f: f64 = f64::from_str(&s)?;
-
-        Ok(Float(f))
-    }
-}
 
 pub struct LazyPrimMST<'a, W, G>
 where
@@ -54,7 +11,7 @@ where
 {
     graph: &'a G,
     // make BinaryHeap to a min-heap
-    pq: BinaryHeap<Reverse<Edge<W>>>,
+    pq: BinaryHeap<Reverse<ByWeight<W>>>,
     marked: Vec<bool>,
     mst: VecDeque<Edge<W>>,
     mst_weight: Option<W>,
@@ -64,7 +21,7 @@ where
 impl<'a, W, G> LazyPrimMST<'a, W, G>
 where
     G: WeightedGraph<W>,
-    W: Display + Ord + Clone + AddAssign,
+    W: Weight,
 {
     pub fn new(graph: &'a G) -> Self {
         let marked = vec![false; graph.v()];
@@ -89,7 +46,7 @@ where
         // 将和节点v相连接的所有未访问的边放入最小堆中
         for &e in self.graph.adj(v).iter() {
             if !self.marked[e.other(v)] {
-                self.pq.push(Reverse(e.clone()));
+                self.pq.push(Reverse(ByWeight(e.clone())));
             }
         }
     }
@@ -99,7 +56,7 @@ where
 
         while !self.pq.is_empty() {
             // 使用最小堆找出已经访问的边中权值最小的边
-            let Reverse(e) = self.pq.pop().unwrap();
+            let Reverse(ByWeight(e)) = self.pq.pop().unwrap();
             // 如果这条边的两端都已经访问过了, 则扔掉这条边
             if self.marked[e.v()] == self.marked[e.w()] {
                 continue;
@@ -116,9 +73,9 @@ where
         }
 
         // 计算最小生成树的权值
-        let mut mst_weight = self.mst[0].weight();
-        for i in 1..self.mst.len() {
-            mst_weight += self.mst[i].weight();
+        let mut mst_weight = W::zero();
+        for e in self.mst.iter() {
+            mst_weight += e.weight();
         }
         self.mst_weight = Some(mst_weight);
     }
@@ -137,7 +94,7 @@ where
         //     Some(v) => Some(v.clone()),
         //     None => None,
         // }
-        self.mst_weight.clone()
+        self.mst_weight
     }
 
     pub fn mst_edges(&mut self) -> Vec<Edge<W>> {
@@ -158,35 +115,47 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::graph::{readgraph, SparseWeightedGraph};
+    use crate::graph::weight::OrderedF64;
+    use crate::graph::{readgraph, DenseWeightedGraph, SparseWeightedGraph};
     use std::path::Path;
 
     #[test]
     fn mst_weight() {
         let filename = Path::new("./src/files/graph/test3.txt");
-        let mut g: SparseWeightedGraph<Float> = SparseWeightedGraph::new(8, false);
+        let mut g: SparseWeightedGraph<OrderedF64> = SparseWeightedGraph::new(8, false);
         readgraph::read_weighted_graph(&mut g, filename).unwrap();
 
         let mut mst = LazyPrimMST::new(&g);
 
-        assert_eq!(mst.result(), Some(Float(1.81)));
+        let weight = mst.result().unwrap();
+        assert!((weight.value() - 1.81).abs() < 1e-9);
+        assert_eq!(mst.mst_edges().len(), 7);
     }
 
     #[test]
-    fn float() {
-        let f1 = Float(0.26);
-        let f2 = Float(0.35);
-        let f3 = Float(0.58);
-        let f4 = Float(0.19);
-        let f5 = Float(0.78);
+    fn integer_weight() {
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(4, false);
+        g.add_edge(0, 1, 4);
+        g.add_edge(1, 2, 1);
+        g.add_edge(2, 3, 2);
+        g.add_edge(3, 0, 3);
+        g.add_edge(0, 2, 5);
 
+        let mut mst = LazyPrimMST::new(&g);
+        assert_eq!(mst.result(), Some(6));
+
+        // 只有一个节点时最小生成树的权值为0
+        let g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(1, false);
+        assert_eq!(LazyPrimMST::new(&g).result(), Some(0));
+    }
+
+    #[test]
+    fn float() {
         let mut min_heap = BinaryHeap::new();
-        min_heap.push(Reverse(f1));
-        min_heap.push(Reverse(f2));
-        min_heap.push(Reverse(f3));
-        min_heap.push(Reverse(f4));
-        min_heap.push(Reverse(f5));
+        for f in [0.26, 0.35, 0.58, 0.19, 0.78] {
+            min_heap.push(Reverse(OrderedF64::new(f)));
+        }
 
-        assert_eq!(min_heap.pop(), Some(Reverse(Float(0.19))));
+        assert_eq!(min_heap.pop(), Some(Reverse(OrderedF64::new(0.19))));
     }
 }
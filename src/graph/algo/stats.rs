@@ -0,0 +1,356 @@
+use crate::graph::algo::components::Component;
+use crate::graph::algo::path::ShortestPath;
+use crate::graph::{Graph, SparseGraph, WeightedGraph};
+
+// 度数的统计信息
+#[derive(Debug, PartialEq)]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub avg: f64,
+    // histogram[d] 表示度数为d的节点个数
+    pub histogram: Vec<usize>,
+}
+
+impl DegreeStats {
+    fn new(degree: &[usize]) -> Self {
+        let min = degree.iter().copied().min().unwrap_or(0);
+        let max = degree.iter().copied().max().unwrap_or(0);
+        let avg = if degree.is_empty() {
+            0.0
+        } else {
+            degree.iter().sum::<usize>() as f64 / degree.len() as f64
+        };
+
+        let mut histogram = vec![0; max + 1];
+        for &d in degree.iter() {
+            histogram[d] += 1;
+        }
+
+        Self {
+            min,
+            max,
+            avg,
+            histogram,
+        }
+    }
+}
+
+// 图的统计信息, 有权图忽略边的权值
+// 度数为邻接表的长度, 连通分量、三角形和聚类系数都按照无向图计算
+pub struct GraphStats {
+    pub vertices: usize,
+    pub edges: usize,
+    pub directed: bool,
+    // 无向图为度数, 有向图为入度与出度之和
+    pub degree: DegreeStats,
+    // 只有有向图才统计入度和出度
+    pub in_degree: Option<DegreeStats>,
+    pub out_degree: Option<DegreeStats>,
+    // 每个连通分量的节点个数, 从大到小排列, 有向图为弱连通分量
+    pub component_sizes: Vec<usize>,
+    // 从节点出发到其他节点的最大距离, 存在不可达的节点时为 None
+    pub eccentricity: Vec<Option<usize>>,
+    pub diameter: Option<usize>,
+    pub radius: Option<usize>,
+    pub triangles: usize,
+    // 每个节点的局部聚类系数
+    pub clustering: Vec<f64>,
+    pub avg_clustering: f64,
+    // 全局聚类系数, 3 * 三角形个数 / 连通三元组个数
+    pub transitivity: f64,
+}
+
+impl GraphStats {
+    pub fn new<G: Graph>(graph: &G) -> Self {
+        let adj: Vec<Vec<usize>> = (0..graph.v()).map(|v| graph.adj(v)).collect();
+        Self::from_adj(&adj, graph.e(), graph.is_directed())
+    }
+
+    pub fn from_weighted<W, G: WeightedGraph<W>>(graph: &G) -> Self
+    where
+        W: Clone,
+    {
+        let adj: Vec<Vec<usize>> = (0..graph.v())
+            .map(|v| graph.adj(v).iter().map(|e| e.other(v)).collect())
+            .collect();
+        Self::from_adj(&adj, graph.e(), graph.is_directed())
+    }
+
+    fn from_adj(adj: &[Vec<usize>], edges: usize, directed: bool) -> Self {
+        let n = adj.len();
+
+        // 重新构建成 SparseGraph, 复用连通分量和最短路径算法
+        let mut g = SparseGraph::new(n, directed);
+        let mut undirected = SparseGraph::new(n, false);
+        for (v, list) in adj.iter().enumerate() {
+            for &w in list.iter() {
+                if directed || v <= w {
+                    g.add_edge(v, w);
+                    undirected.add_edge(v, w);
+                }
+            }
+        }
+
+        let out_degree: Vec<usize> = adj.iter().map(|list| list.len()).collect();
+        let mut in_degree = vec![0; n];
+        for &w in adj.iter().flatten() {
+            in_degree[w] += 1;
+        }
+        let degree: Vec<usize> = if directed {
+            (0..n).map(|v| in_degree[v] + out_degree[v]).collect()
+        } else {
+            out_degree.clone()
+        };
+
+        let mut component = Component::new(&undirected);
+        let mut component_sizes = component.sizes();
+        component_sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+        let eccentricity: Vec<Option<usize>> = (0..n)
+            .map(|v| {
+                let mut bfs = ShortestPath::new(&g, v);
+                let lengths: Option<Vec<usize>> = (0..n).map(|w| bfs.length(w)).collect();
+                lengths.and_then(|lengths| lengths.into_iter().max())
+            })
+            .collect();
+        let (diameter, radius) = if eccentricity.iter().all(|e| e.is_some()) {
+            (
+                eccentricity.iter().copied().max().flatten(),
+                eccentricity.iter().copied().min().flatten(),
+            )
+        } else {
+            (None, None)
+        };
+
+        let (triangles, clustering, transitivity) = Self::triangles(&undirected);
+        let avg_clustering = if n == 0 {
+            0.0
+        } else {
+            clustering.iter().sum::<f64>() / n as f64
+        };
+
+        Self {
+            vertices: n,
+            edges,
+            directed,
+            degree: DegreeStats::new(&degree),
+            in_degree: if directed {
+                Some(DegreeStats::new(&in_degree))
+            } else {
+                None
+            },
+            out_degree: if directed {
+                Some(DegreeStats::new(&out_degree))
+            } else {
+                None
+            },
+            component_sizes,
+            eccentricity,
+            diameter,
+            radius,
+            triangles,
+            clustering,
+            avg_clustering,
+            transitivity,
+        }
+    }
+
+    // 统计三角形个数, 局部聚类系数和全局聚类系数
+    // 忽略自环和平行边
+    fn triangles(g: &SparseGraph) -> (usize, Vec<f64>, f64) {
+        let n = g.v();
+        let neighbors: Vec<Vec<usize>> = (0..n)
+            .map(|v| {
+                let mut list: Vec<usize> = g.adj(v).into_iter().filter(|&w| w != v).collect();
+                list.sort_unstable();
+                list.dedup();
+                list
+            })
+            .collect();
+
+        // local[v] 表示包含节点v的三角形个数
+        let mut local = vec![0; n];
+        let mut triangles = 0;
+        for v in 0..n {
+            for (i, &a) in neighbors[v].iter().enumerate() {
+                for &b in neighbors[v][i + 1..].iter() {
+                    if neighbors[a].binary_search(&b).is_ok() {
+                        local[v] += 1;
+                        if v < a {
+                            triangles += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut triads = 0;
+        let clustering = (0..n)
+            .map(|v| {
+                let d = neighbors[v].len();
+                let pairs = d * d.saturating_sub(1) / 2;
+                triads += pairs;
+                if pairs == 0 {
+                    0.0
+                } else {
+                    local[v] as f64 / pairs as f64
+                }
+            })
+            .collect();
+
+        let transitivity = if triads == 0 {
+            0.0
+        } else {
+            (3 * triangles) as f64 / triads as f64
+        };
+        (triangles, clustering, transitivity)
+    }
+
+    // 以表格的形式打印统计信息
+    pub fn show(&self) {
+        fn row(name: &str, value: String) {
+            println!("| {:<20} | {:>24} |", name, value);
+        }
+        fn degree_rows(prefix: &str, d: &DegreeStats) {
+            row(&format!("{} min", prefix), d.min.to_string());
+            row(&format!("{} max", prefix), d.max.to_string());
+            row(&format!("{} avg", prefix), format!("{:.4}", d.avg));
+            row(
+                &format!("{} histogram", prefix),
+                format!("{:?}", d.histogram),
+            );
+        }
+        fn optional(value: Option<usize>) -> String {
+            match value {
+                Some(v) => v.to_string(),
+                None => "inf".to_string(),
+            }
+        }
+
+        row("vertices", self.vertices.to_string());
+        row("edges", self.edges.to_string());
+        row("directed", self.directed.to_string());
+        degree_rows("degree", &self.degree);
+        if let Some(d) = &self.in_degree {
+            degree_rows("in degree", d);
+        }
+        if let Some(d) = &self.out_degree {
+            degree_rows("out degree", d);
+        }
+        row("components", self.component_sizes.len().to_string());
+        row("component sizes", format!("{:?}", self.component_sizes));
+        let eccentricity: Vec<String> = self.eccentricity.iter().map(|&e| optional(e)).collect();
+        row("eccentricity", eccentricity.join(" "));
+        row("diameter", optional(self.diameter));
+        row("radius", optional(self.radius));
+        row("triangles", self.triangles.to_string());
+        let clustering: Vec<String> = self
+            .clustering
+            .iter()
+            .map(|c| format!("{:.2}", c))
+            .collect();
+        row("clustering", clustering.join(" "));
+        row("avg clustering", format!("{:.4}", self.avg_clustering));
+        row("transitivity", format!("{:.4}", self.transitivity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{readgraph, DenseGraph, SparseWeightedGraph};
+    use std::path::Path;
+
+    #[test]
+    fn complete_graph() {
+        let mut g = DenseGraph::new(4, false);
+        for i in 0..4 {
+            for j in i + 1..4 {
+                g.add_edge(i, j);
+            }
+        }
+
+        let stats = GraphStats::new(&g);
+        assert_eq!(stats.edges, 6);
+        assert_eq!(stats.degree.min, 3);
+        assert_eq!(stats.degree.max, 3);
+        assert_eq!(stats.degree.histogram, vec![0, 0, 0, 4]);
+        assert_eq!(stats.component_sizes, vec![4]);
+        assert_eq!(stats.diameter, Some(1));
+        assert_eq!(stats.radius, Some(1));
+        assert_eq!(stats.triangles, 4);
+        assert_eq!(stats.clustering, vec![1.0; 4]);
+        assert_eq!(stats.transitivity, 1.0);
+        assert_eq!(stats.in_degree, None);
+    }
+
+    #[test]
+    fn path_and_components() {
+        // 路径 0 - 1 - 2 - 3 和孤立节点 4
+        let mut g = SparseGraph::new(5, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+
+        let stats = GraphStats::new(&g);
+        assert_eq!(stats.degree.avg, 6.0 / 5.0);
+        assert_eq!(stats.degree.histogram, vec![1, 2, 2]);
+        assert_eq!(stats.component_sizes, vec![4, 1]);
+        assert_eq!(stats.eccentricity[0], None);
+        assert_eq!(stats.diameter, None);
+        assert_eq!(stats.triangles, 0);
+        assert_eq!(stats.avg_clustering, 0.0);
+
+        g.add_edge(4, 0);
+        let stats = GraphStats::new(&g);
+        assert_eq!(
+            stats.eccentricity,
+            vec![Some(3), Some(2), Some(3), Some(4), Some(4)]
+        );
+        assert_eq!(stats.diameter, Some(4));
+        assert_eq!(stats.radius, Some(2));
+
+        // test1.txt 有3个联通分量
+        let filename = Path::new("./src/files/graph/test1.txt");
+        let mut g = SparseGraph::new(13, false);
+        readgraph::read(&mut g, filename).unwrap();
+        let stats = GraphStats::new(&g);
+        assert_eq!(stats.component_sizes, vec![7, 4, 2]);
+        assert_eq!(stats.triangles, 2);
+    }
+
+    #[test]
+    fn directed() {
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(0, 2);
+
+        let stats = GraphStats::new(&g);
+        assert_eq!(stats.degree.histogram, vec![0, 0, 1, 2]);
+        let in_degree = stats.in_degree.unwrap();
+        let out_degree = stats.out_degree.unwrap();
+        assert_eq!((in_degree.min, in_degree.max), (1, 2));
+        assert_eq!((out_degree.min, out_degree.max), (1, 2));
+        assert_eq!(stats.eccentricity, vec![Some(1), Some(2), Some(2)]);
+        assert_eq!(stats.radius, Some(1));
+        assert_eq!(stats.triangles, 1);
+        assert_eq!(stats.component_sizes, vec![3]);
+    }
+
+    #[test]
+    fn weighted() {
+        let filename = Path::new("./src/files/graph/test3.txt");
+        let mut g: SparseWeightedGraph<f64> = SparseWeightedGraph::new(8, false);
+        readgraph::read_weighted_graph(&mut g, filename).unwrap();
+
+        let stats = GraphStats::from_weighted(&g);
+        assert_eq!(stats.vertices, 8);
+        assert_eq!(stats.edges, 16);
+        assert_eq!(stats.degree.histogram.iter().sum::<usize>(), 8);
+        assert_eq!(stats.component_sizes, vec![8]);
+        assert!(stats.diameter.is_some());
+    }
+}
@@ -0,0 +1,309 @@
+use crate::graph::{Graph, WeightedGraph};
+
+// 由已有的图生成新的图, 返回的图类型由调用方指定, 例如 SparseGraph 或者 DenseGraph
+
+// 列出图中所有的边, 无向图中每条边只出现一次
+pub(crate) fn edges<G: Graph>(graph: &G) -> Vec<(usize, usize)> {
+    let mut ret = Vec::new();
+    for v in 0..graph.v() {
+        for w in graph.adj(v) {
+            if graph.is_directed() || v <= w {
+                ret.push((v, w));
+            }
+        }
+    }
+    ret
+}
+
+pub(crate) fn weighted_edges<W, G>(graph: &G) -> Vec<(usize, usize, W)>
+where
+    W: Clone,
+    G: WeightedGraph<W>,
+{
+    let mut ret = Vec::new();
+    for v in 0..graph.v() {
+        for edge in graph.adj(v) {
+            let w = edge.other(v);
+            if graph.is_directed() || v <= w {
+                ret.push((v, w, edge.weight()));
+            }
+        }
+    }
+    ret
+}
+
+// 反向图, 有向图中的每条边 v -> w 变为 w -> v, 无向图返回相同的图
+pub fn reverse<G: Graph, H: Graph>(graph: &G) -> H {
+    let mut ret = H::new(graph.v(), graph.is_directed());
+    for (v, w) in edges(graph) {
+        ret.add_edge(w, v);
+    }
+    ret
+}
+
+pub fn reverse_weighted<W, G, H>(graph: &G) -> H
+where
+    W: Clone,
+    G: WeightedGraph<W>,
+    H: WeightedGraph<W>,
+{
+    let mut ret = H::new(graph.v(), graph.is_directed());
+    for (v, w, weight) in weighted_edges(graph) {
+        ret.add_edge(w, v, weight);
+    }
+    ret
+}
+
+// vertices 中的节点在新图中重新编号, vertices[i] 在新图中的编号为 i
+// 返回旧编号到新编号的映射, 不在子图中的节点为 None
+fn remap(n: usize, vertices: &[usize]) -> Vec<Option<usize>> {
+    let mut index = vec![None; n];
+    for (i, &v) in vertices.iter().enumerate() {
+        assert!(v < n);
+        assert!(index[v].is_none(), "duplicate vertex {}", v);
+        index[v] = Some(i);
+    }
+    index
+}
+
+// 导出子图, 保留两个端点都在 vertices 中的边
+pub fn induced_subgraph<G: Graph, H: Graph>(graph: &G, vertices: &[usize]) -> H {
+    let index = remap(graph.v(), vertices);
+    let mut ret = H::new(vertices.len(), graph.is_directed());
+    for (v, w) in edges(graph) {
+        if let (Some(a), Some(b)) = (index[v], index[w]) {
+            ret.add_edge(a, b);
+        }
+    }
+    ret
+}
+
+pub fn induced_subgraph_weighted<W, G, H>(graph: &G, vertices: &[usize]) -> H
+where
+    W: Clone,
+    G: WeightedGraph<W>,
+    H: WeightedGraph<W>,
+{
+    let index = remap(graph.v(), vertices);
+    let mut ret = H::new(vertices.len(), graph.is_directed());
+    for (v, w, weight) in weighted_edges(graph) {
+        if let (Some(a), Some(b)) = (index[v], index[w]) {
+            ret.add_edge(a, b, weight);
+        }
+    }
+    ret
+}
+
+// 补图, 两个不同节点之间原来没有边则连边, 不生成自环
+pub fn complement<G: Graph, H: Graph>(graph: &G) -> H {
+    let n = graph.v();
+    let directed = graph.is_directed();
+    let mut ret = H::new(n, directed);
+    for v in 0..n {
+        let start = if directed { 0 } else { v + 1 };
+        for w in start..n {
+            if v != w && !graph.has_edge(v, w) {
+                ret.add_edge(v, w);
+            }
+        }
+    }
+    ret
+}
+
+// 不相交并, b中的节点编号整体加上 a.v()
+pub fn disjoint_union<A: Graph, B: Graph, H: Graph>(a: &A, b: &B) -> H {
+    assert_eq!(a.is_directed(), b.is_directed());
+
+    let offset = a.v();
+    let mut ret = H::new(a.v() + b.v(), a.is_directed());
+    for (v, w) in edges(a) {
+        ret.add_edge(v, w);
+    }
+    for (v, w) in edges(b) {
+        ret.add_edge(v + offset, w + offset);
+    }
+    ret
+}
+
+pub fn disjoint_union_weighted<W, A, B, H>(a: &A, b: &B) -> H
+where
+    W: Clone,
+    A: WeightedGraph<W>,
+    B: WeightedGraph<W>,
+    H: WeightedGraph<W>,
+{
+    assert_eq!(a.is_directed(), b.is_directed());
+
+    let offset = a.v();
+    let mut ret = H::new(a.v() + b.v(), a.is_directed());
+    for (v, w, weight) in weighted_edges(a) {
+        ret.add_edge(v, w, weight);
+    }
+    for (v, w, weight) in weighted_edges(b) {
+        ret.add_edge(v + offset, w + offset, weight);
+    }
+    ret
+}
+
+// 线图, 原图中的每条边是新图中的一个节点
+// 无向图中两条边有公共端点则相连, 有向图中 u -> v 连向 v -> w
+// 返回新图和新图中每个节点对应的原图中的边
+pub fn line_graph<G: Graph, H: Graph>(graph: &G) -> (H, Vec<(usize, usize)>) {
+    let edges = edges(graph);
+    let directed = graph.is_directed();
+
+    // incident[v] 表示以v为端点(有向图中为起点)的边的编号
+    let mut incident = vec![vec![]; graph.v()];
+    for (id, &(v, w)) in edges.iter().enumerate() {
+        incident[v].push(id);
+        if !directed && v != w {
+            incident[w].push(id);
+        }
+    }
+
+    let mut ret = H::new(edges.len(), directed);
+    if directed {
+        for (id, &(_, w)) in edges.iter().enumerate() {
+            for &next in incident[w].iter() {
+                ret.add_edge(id, next);
+            }
+        }
+    } else {
+        for list in incident.iter() {
+            for (i, &a) in list.iter().enumerate() {
+                for &b in list[i + 1..].iter() {
+                    ret.add_edge(a, b);
+                }
+            }
+        }
+    }
+    (ret, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        readgraph, DenseGraph, DenseWeightedGraph, SparseGraph, SparseWeightedGraph,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn reverse_graph() {
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        let r: DenseGraph = reverse(&g);
+        assert!(r.is_directed());
+        assert!(r.has_edge(1, 0) && r.has_edge(2, 1));
+        assert!(!r.has_edge(0, 1));
+        assert_eq!(r.e(), 2);
+
+        let mut g: SparseWeightedGraph<f64> = SparseWeightedGraph::new(3, true);
+        g.add_edge(0, 1, 0.5);
+        g.add_edge(1, 2, 1.5);
+        let r: DenseWeightedGraph<f64> = reverse_weighted(&g);
+        assert_eq!(r.adj(2)[0].weight(), 1.5);
+        assert_eq!(r.adj(2)[0].other(2), 1);
+        assert!(!r.has_edge(0, 1));
+    }
+
+    #[test]
+    fn subgraph() {
+        let filename = Path::new("./src/files/graph/test1.txt");
+        let mut g = SparseGraph::new(13, false);
+        readgraph::read(&mut g, filename).unwrap();
+
+        // 0-5, 5-4, 4-3, 5-3
+        let sub: SparseGraph = induced_subgraph(&g, &[3, 0, 5, 4]);
+        assert_eq!(sub.v(), 4);
+        assert_eq!(sub.e(), 4);
+        assert!(sub.has_edge(1, 2));
+        assert!(sub.has_edge(0, 3));
+        assert!(!sub.has_edge(1, 3));
+
+        let mut g: SparseWeightedGraph<u32> = SparseWeightedGraph::new(4, false);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 2, 2);
+        g.add_edge(2, 3, 3);
+        let sub: SparseWeightedGraph<u32> = induced_subgraph_weighted(&g, &[3, 2]);
+        assert_eq!(sub.e(), 1);
+        assert_eq!(sub.adj(0)[0].weight(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate vertex")]
+    fn subgraph_duplicate() {
+        let g = SparseGraph::new(3, false);
+        let _: SparseGraph = induced_subgraph(&g, &[0, 1, 0]);
+    }
+
+    #[test]
+    fn complement_graph() {
+        // 路径 0 - 1 - 2 - 3 的补图为 0-2, 0-3, 1-3
+        let mut g = SparseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        let c: DenseGraph = complement(&g);
+        assert_eq!(c.e(), 3);
+        assert!(c.has_edge(0, 2) && c.has_edge(3, 0) && c.has_edge(1, 3));
+        assert!(!c.has_edge(0, 1) && !c.has_edge(0, 0));
+
+        let mut g = DenseGraph::new(3, true);
+        g.add_edge(0, 1);
+        let c: SparseGraph = complement(&g);
+        assert_eq!(c.e(), 5);
+        assert!(c.has_edge(1, 0) && !c.has_edge(0, 1));
+    }
+
+    #[test]
+    fn union() {
+        let mut a = SparseGraph::new(2, false);
+        a.add_edge(0, 1);
+        let mut b = DenseGraph::new(3, false);
+        b.add_edge(0, 2);
+        let u: SparseGraph = disjoint_union(&a, &b);
+        assert_eq!(u.v(), 5);
+        assert_eq!(u.e(), 2);
+        assert!(u.has_edge(0, 1) && u.has_edge(2, 4));
+
+        let mut a: SparseWeightedGraph<u32> = SparseWeightedGraph::new(2, true);
+        a.add_edge(1, 0, 7);
+        let b: SparseWeightedGraph<u32> = SparseWeightedGraph::new(1, true);
+        let u: DenseWeightedGraph<u32> = disjoint_union_weighted(&a, &b);
+        assert_eq!(u.v(), 3);
+        assert_eq!(u.adj(1)[0].weight(), 7);
+    }
+
+    #[test]
+    fn line() {
+        // 星型图的线图为三角形
+        let mut g = SparseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(0, 3);
+        let (l, edges): (SparseGraph, _) = line_graph(&g);
+        assert_eq!(edges, vec![(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(l.v(), 3);
+        assert_eq!(l.e(), 3);
+
+        // 路径的线图为少一个节点的路径
+        let mut g = DenseGraph::new(4, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        let (l, _): (DenseGraph, _) = line_graph(&g);
+        assert_eq!(l.e(), 2);
+
+        // 有向环的线图也是有向环
+        let mut g = SparseGraph::new(3, true);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        let (l, edges): (SparseGraph, _) = line_graph(&g);
+        assert_eq!(l.e(), 3);
+        assert!(l.has_edge(0, 1) && l.has_edge(1, 2) && l.has_edge(2, 0));
+        assert_eq!(edges[2], (2, 0));
+    }
+}
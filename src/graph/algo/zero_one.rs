@@ -0,0 +1,168 @@
+use crate::graph::weight::IntegerWeight;
+use crate::graph::WeightedGraph;
+use std::collections::VecDeque;
+
+// 边的权值只有0和1时的单源最短路径 - 0-1 BFS, O(V + E)
+// 权值为0的边放到队首, 权值为1的边放到队尾, 队列中的距离始终单调不减
+pub struct ZeroOneBfs<'a, W, G>
+where
+    G: WeightedGraph<W>,
+{
+    graph: &'a G,
+    start: usize,
+    dist: Vec<Option<W>>,
+    from: Vec<Option<usize>>,
+    initialized: bool,
+}
+
+impl<'a, W, G> ZeroOneBfs<'a, W, G>
+where
+    W: IntegerWeight,
+    G: WeightedGraph<W>,
+{
+    pub fn new(graph: &'a G, start: usize) -> Self {
+        assert!(start < graph.v());
+        Self {
+            graph,
+            start,
+            dist: vec![None; graph.v()],
+            from: vec![None; graph.v()],
+            initialized: false,
+        }
+    }
+
+    fn bfs(&mut self) {
+        let zero = W::zero();
+        let one = W::one();
+        let mut marked = vec![false; self.graph.v()];
+        let mut deque = VecDeque::new();
+        self.dist[self.start] = Some(zero);
+        deque.push_back(self.start);
+
+        while let Some(v) = deque.pop_front() {
+            // 一个节点可能入队多次, 只处理第一次出队
+            if marked[v] {
+                continue;
+            }
+            marked[v] = true;
+
+            let d = self.dist[v].unwrap();
+            for edge in self.graph.adj(v) {
+                let w = edge.other(v);
+                let weight = edge.weight();
+                assert!(
+                    weight == zero || weight == one,
+                    "weight {} is not 0 or 1",
+                    weight
+                );
+                let alt = d + weight;
+                if !marked[w] && self.dist[w].is_none_or(|old| alt < old) {
+                    self.dist[w] = Some(alt);
+                    self.from[w] = Some(v);
+                    if weight == zero {
+                        deque.push_front(w);
+                    } else {
+                        deque.push_back(w);
+                    }
+                }
+            }
+        }
+    }
+
+    fn initialize(&mut self) {
+        self.bfs();
+        self.initialized = true;
+    }
+
+    // 查询从s点到w点是否有路径
+    pub fn has_path(&mut self, w: usize) -> bool {
+        assert!(w < self.graph.v());
+        if !self.initialized {
+            self.initialize();
+        }
+        self.dist[w].is_some()
+    }
+
+    // 查询从s点到w点的最短路径, 存放在vec中
+    pub fn path(&mut self, w: usize, vec: &mut Vec<usize>) {
+        assert!(self.has_path(w));
+
+        let mut stack = Vec::new();
+        let mut p = Some(w);
+        while let Some(v) = p {
+            stack.push(v);
+            p = self.from[v];
+        }
+        while let Some(v) = stack.pop() {
+            vec.push(v);
+        }
+    }
+
+    // 查询从s点到w点的最短路径长度, 不可达时返回 None
+    pub fn length(&mut self, w: usize) -> Option<W> {
+        assert!(w < self.graph.v());
+        if !self.initialized {
+            self.initialize();
+        }
+        self.dist[w]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::algo::dijkstra::Dijkstra;
+    use crate::graph::{DenseWeightedGraph, SparseWeightedGraph};
+    use crate::util::random;
+    use rand::Rng;
+
+    #[test]
+    fn zero_one() {
+        let mut g: SparseWeightedGraph<u32> = SparseWeightedGraph::new(5, true);
+        g.add_edge(0, 1, 1);
+        g.add_edge(0, 2, 0);
+        g.add_edge(2, 1, 0);
+        g.add_edge(1, 3, 1);
+        g.add_edge(2, 3, 1);
+
+        let mut bfs = ZeroOneBfs::new(&g, 0);
+        assert_eq!(bfs.length(1), Some(0));
+        assert_eq!(bfs.length(3), Some(1));
+        assert!(!bfs.has_path(4));
+        let mut path = Vec::new();
+        bfs.path(1, &mut path);
+        assert_eq!(path, vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn random_against_dijkstra() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for _ in 0..20 {
+                let n = rng.gen_range(1, 50);
+                let mut g: SparseWeightedGraph<usize> = SparseWeightedGraph::new(n, rng.gen());
+                for _ in 0..rng.gen_range(0, 4 * n) {
+                    g.add_edge(
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, n),
+                        rng.gen_range(0, 2),
+                    );
+                }
+
+                let mut bfs = ZeroOneBfs::new(&g, 0);
+                let mut dijkstra = Dijkstra::new(&g, 0);
+                for v in 0..n {
+                    assert_eq!(bfs.length(v), dijkstra.length(v));
+                }
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "is not 0 or 1")]
+    fn invalid_weight() {
+        let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(2, false);
+        g.add_edge(0, 1, 2);
+        ZeroOneBfs::new(&g, 0).has_path(1);
+    }
+}
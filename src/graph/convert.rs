@@ -0,0 +1,485 @@
+use crate::graph::algo::transform::{edges, weighted_edges};
+use crate::graph::{
+    DenseGraph, DenseWeightedGraph, Edge, Graph, SparseGraph, SparseWeightedGraph, WeightedGraph,
+};
+use std::fmt;
+
+// 图的表示方式之间的转换
+// 稠密图中平行边会合并为一条边, 有权稠密图中保留最后加入的边
+
+// 复制图中所有的边到新的表示方式
+pub fn convert<G: Graph, H: Graph>(graph: &G) -> H {
+    let mut ret = H::new(graph.v(), graph.is_directed());
+    for (v, w) in edges(graph) {
+        ret.add_edge(v, w);
+    }
+    ret
+}
+
+pub fn convert_weighted<T, G, H>(graph: &G) -> H
+where
+    T: Clone,
+    G: WeightedGraph<T>,
+    H: WeightedGraph<T>,
+{
+    let mut ret = H::new(graph.v(), graph.is_directed());
+    for (v, w, weight) in weighted_edges(graph) {
+        ret.add_edge(v, w, weight);
+    }
+    ret
+}
+
+// 有权图转换为无权图, 丢弃边的权值
+pub fn to_unweighted<T, G, H>(graph: &G) -> H
+where
+    T: Clone,
+    G: WeightedGraph<T>,
+    H: Graph,
+{
+    let mut ret = H::new(graph.v(), graph.is_directed());
+    for (v, w, _) in weighted_edges(graph) {
+        ret.add_edge(v, w);
+    }
+    ret
+}
+
+// 无权图转换为有权图, 所有边的权值都为weight
+pub fn to_weighted<T, G, H>(graph: &G, weight: T) -> H
+where
+    T: Clone,
+    G: Graph,
+    H: WeightedGraph<T>,
+{
+    let mut ret = H::new(graph.v(), graph.is_directed());
+    for (v, w) in edges(graph) {
+        ret.add_edge(v, w, weight.clone());
+    }
+    ret
+}
+
+impl From<&DenseGraph> for SparseGraph {
+    fn from(graph: &DenseGraph) -> Self {
+        convert(graph)
+    }
+}
+
+impl From<&SparseGraph> for DenseGraph {
+    fn from(graph: &SparseGraph) -> Self {
+        convert(graph)
+    }
+}
+
+impl<T> From<&DenseWeightedGraph<T>> for SparseWeightedGraph<T>
+where
+    T: Copy + fmt::Display,
+{
+    fn from(graph: &DenseWeightedGraph<T>) -> Self {
+        convert_weighted(graph)
+    }
+}
+
+impl<T> From<&SparseWeightedGraph<T>> for DenseWeightedGraph<T>
+where
+    T: Copy + fmt::Display,
+{
+    fn from(graph: &SparseWeightedGraph<T>) -> Self {
+        convert_weighted(graph)
+    }
+}
+
+impl<T> From<&DenseWeightedGraph<T>> for DenseGraph
+where
+    T: Copy + fmt::Display,
+{
+    fn from(graph: &DenseWeightedGraph<T>) -> Self {
+        to_unweighted(graph)
+    }
+}
+
+impl<T> From<&DenseWeightedGraph<T>> for SparseGraph
+where
+    T: Copy + fmt::Display,
+{
+    fn from(graph: &DenseWeightedGraph<T>) -> Self {
+        to_unweighted(graph)
+    }
+}
+
+impl<T> From<&SparseWeightedGraph<T>> for DenseGraph
+where
+    T: Copy + fmt::Display,
+{
+    fn from(graph: &SparseWeightedGraph<T>) -> Self {
+        to_unweighted(graph)
+    }
+}
+
+impl<T> From<&SparseWeightedGraph<T>> for SparseGraph
+where
+    T: Copy + fmt::Display,
+{
+    fn from(graph: &SparseWeightedGraph<T>) -> Self {
+        to_unweighted(graph)
+    }
+}
+
+// 无权图转换为有权图时, 边的权值为 T::default()
+impl<T> From<&DenseGraph> for DenseWeightedGraph<T>
+where
+    T: Copy + Default + fmt::Display,
+{
+    fn from(graph: &DenseGraph) -> Self {
+        to_weighted(graph, T::default())
+    }
+}
+
+impl<T> From<&DenseGraph> for SparseWeightedGraph<T>
+where
+    T: Copy + Default + fmt::Display,
+{
+    fn from(graph: &DenseGraph) -> Self {
+        to_weighted(graph, T::default())
+    }
+}
+
+impl<T> From<&SparseGraph> for DenseWeightedGraph<T>
+where
+    T: Copy + Default + fmt::Display,
+{
+    fn from(graph: &SparseGraph) -> Self {
+        to_weighted(graph, T::default())
+    }
+}
+
+impl<T> From<&SparseGraph> for SparseWeightedGraph<T>
+where
+    T: Copy + Default + fmt::Display,
+{
+    fn from(graph: &SparseGraph) -> Self {
+        to_weighted(graph, T::default())
+    }
+}
+
+// 边的密度超过该值时使用邻接矩阵
+// 邻接矩阵每个节点对占1个字节, 邻接表每条边占8个字节且无向图存两次
+const DENSITY_THRESHOLD: f64 = 0.25;
+
+// 图的密度, 即边数与最大可能边数之比
+pub fn density(n: usize, m: usize, directed: bool) -> f64 {
+    if n < 2 {
+        return 0.0;
+    }
+    let max = if directed {
+        n * (n - 1)
+    } else {
+        n * (n - 1) / 2
+    };
+    m as f64 / max as f64
+}
+
+// 根据密度自动选择表示方式的无权图
+pub enum AutoGraph {
+    Dense(DenseGraph),
+    Sparse(SparseGraph),
+}
+
+impl Graph for AutoGraph {
+    // 边数未知, 默认使用邻接表
+    fn new(n: usize, directed: bool) -> Self {
+        AutoGraph::Sparse(SparseGraph::new(n, directed))
+    }
+
+    fn v(&self) -> usize {
+        match self {
+            AutoGraph::Dense(g) => g.v(),
+            AutoGraph::Sparse(g) => g.v(),
+        }
+    }
+
+    fn e(&self) -> usize {
+        match self {
+            AutoGraph::Dense(g) => g.e(),
+            AutoGraph::Sparse(g) => g.e(),
+        }
+    }
+
+    fn is_directed(&self) -> bool {
+        match self {
+            AutoGraph::Dense(g) => g.is_directed(),
+            AutoGraph::Sparse(g) => g.is_directed(),
+        }
+    }
+
+    fn add_edge(&mut self, v: usize, w: usize) {
+        match self {
+            AutoGraph::Dense(g) => g.add_edge(v, w),
+            AutoGraph::Sparse(g) => g.add_edge(v, w),
+        }
+    }
+
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        match self {
+            AutoGraph::Dense(g) => g.has_edge(v, w),
+            AutoGraph::Sparse(g) => g.has_edge(v, w),
+        }
+    }
+
+    fn adj(&self, v: usize) -> Vec<usize> {
+        match self {
+            AutoGraph::Dense(g) => g.adj(v),
+            AutoGraph::Sparse(g) => g.adj(v),
+        }
+    }
+
+    fn show(&self) {
+        match self {
+            AutoGraph::Dense(g) => g.show(),
+            AutoGraph::Sparse(g) => g.show(),
+        }
+    }
+}
+
+// 根据密度自动选择表示方式的有权图
+pub enum AutoWeightedGraph<T> {
+    Dense(DenseWeightedGraph<T>),
+    Sparse(SparseWeightedGraph<T>),
+}
+
+impl<T> WeightedGraph<T> for AutoWeightedGraph<T>
+where
+    T: Copy + fmt::Display,
+{
+    // 边数未知, 默认使用邻接表
+    fn new(n: usize, directed: bool) -> Self {
+        AutoWeightedGraph::Sparse(SparseWeightedGraph::new(n, directed))
+    }
+
+    fn v(&self) -> usize {
+        match self {
+            AutoWeightedGraph::Dense(g) => g.v(),
+            AutoWeightedGraph::Sparse(g) => g.v(),
+        }
+    }
+
+    fn e(&self) -> usize {
+        match self {
+            AutoWeightedGraph::Dense(g) => g.e(),
+            AutoWeightedGraph::Sparse(g) => g.e(),
+        }
+    }
+
+    fn is_directed(&self) -> bool {
+        match self {
+            AutoWeightedGraph::Dense(g) => g.is_directed(),
+            AutoWeightedGraph::Sparse(g) => g.is_directed(),
+        }
+    }
+
+    fn add_edge(&mut self, v: usize, w: usize, weight: T) {
+        match self {
+            AutoWeightedGraph::Dense(g) => g.add_edge(v, w, weight),
+            AutoWeightedGraph::Sparse(g) => g.add_edge(v, w, weight),
+        }
+    }
+
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        match self {
+            AutoWeightedGraph::Dense(g) => g.has_edge(v, w),
+            AutoWeightedGraph::Sparse(g) => g.has_edge(v, w),
+        }
+    }
+
+    fn adj(&self, v: usize) -> Vec<&Edge<T>> {
+        match self {
+            AutoWeightedGraph::Dense(g) => g.adj(v),
+            AutoWeightedGraph::Sparse(g) => g.adj(v),
+        }
+    }
+
+    fn show(&self) {
+        match self {
+            AutoWeightedGraph::Dense(g) => g.show(),
+            AutoWeightedGraph::Sparse(g) => g.show(),
+        }
+    }
+}
+
+// 先收集所有的边, 在 build 时根据密度选择表示方式
+pub struct GraphBuilder<T> {
+    n: usize,
+    directed: bool,
+    threshold: f64,
+    edges: Vec<(usize, usize, T)>,
+}
+
+impl<T: Copy> GraphBuilder<T> {
+    pub fn new(n: usize, directed: bool) -> Self {
+        Self {
+            n,
+            directed,
+            threshold: DENSITY_THRESHOLD,
+            edges: Vec::new(),
+        }
+    }
+
+    // 设置选择邻接矩阵的密度阈值
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn add_edge(&mut self, v: usize, w: usize, weight: T) -> &mut Self {
+        assert!(v < self.n && w < self.n);
+        self.edges.push((v, w, weight));
+        self
+    }
+
+    fn is_dense(&self) -> bool {
+        density(self.n, self.edges.len(), self.directed) > self.threshold
+    }
+}
+
+impl<T> GraphBuilder<T>
+where
+    T: Copy + fmt::Display,
+{
+    // 生成有权图
+    pub fn build_weighted(&self) -> AutoWeightedGraph<T> {
+        let mut ret = if self.is_dense() {
+            AutoWeightedGraph::Dense(DenseWeightedGraph::new(self.n, self.directed))
+        } else {
+            AutoWeightedGraph::Sparse(SparseWeightedGraph::new(self.n, self.directed))
+        };
+        for &(v, w, weight) in self.edges.iter() {
+            ret.add_edge(v, w, weight);
+        }
+        ret
+    }
+}
+
+impl GraphBuilder<()> {
+    pub fn add_unweighted_edge(&mut self, v: usize, w: usize) -> &mut Self {
+        self.add_edge(v, w, ())
+    }
+
+    // 生成无权图
+    pub fn build(&self) -> AutoGraph {
+        let mut ret = if self.is_dense() {
+            AutoGraph::Dense(DenseGraph::new(self.n, self.directed))
+        } else {
+            AutoGraph::Sparse(SparseGraph::new(self.n, self.directed))
+        };
+        for &(v, w, _) in self.edges.iter() {
+            ret.add_edge(v, w);
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::readgraph;
+    use std::path::Path;
+
+    #[test]
+    fn dense_and_sparse() {
+        let filename = Path::new("./src/files/graph/test1.txt");
+        let mut g = SparseGraph::new(13, false);
+        readgraph::read(&mut g, filename).unwrap();
+
+        let dense = DenseGraph::from(&g);
+        assert_eq!(dense.v(), 13);
+        assert_eq!(dense.e(), g.e());
+        for v in 0..13 {
+            assert_eq!(dense.adj(v).len(), g.adj(v).len());
+        }
+
+        let sparse = SparseGraph::from(&dense);
+        assert_eq!(sparse.e(), g.e());
+        assert!(sparse.has_edge(0, 5) && sparse.has_edge(5, 0));
+
+        let mut g = DenseGraph::new(3, true);
+        g.add_edge(0, 1);
+        let sparse = SparseGraph::from(&g);
+        assert!(sparse.is_directed());
+        assert!(!sparse.has_edge(1, 0));
+    }
+
+    #[test]
+    fn weighted() {
+        let filename = Path::new("./src/files/graph/test3.txt");
+        let mut g: SparseWeightedGraph<f64> = SparseWeightedGraph::new(8, false);
+        readgraph::read_weighted_graph(&mut g, filename).unwrap();
+
+        let dense = DenseWeightedGraph::from(&g);
+        assert_eq!(dense.e(), 16);
+        let sparse = SparseWeightedGraph::from(&dense);
+        for v in 0..8 {
+            let mut a: Vec<(usize, f64)> =
+                g.adj(v).iter().map(|e| (e.other(v), e.weight())).collect();
+            let mut b: Vec<(usize, f64)> = sparse
+                .adj(v)
+                .iter()
+                .map(|e| (e.other(v), e.weight()))
+                .collect();
+            a.sort_by_key(|x| x.0);
+            b.sort_by_key(|x| x.0);
+            assert_eq!(a, b);
+        }
+
+        // 有权图转换为无权图
+        let unweighted = SparseGraph::from(&g);
+        assert_eq!(unweighted.e(), 16);
+        assert!(unweighted.has_edge(4, 5));
+        let unweighted = DenseGraph::from(&dense);
+        assert!(unweighted.has_edge(5, 4));
+
+        // 无权图转换为有权图
+        let mut g = SparseGraph::new(3, false);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        let w: DenseWeightedGraph<u32> = DenseWeightedGraph::from(&g);
+        assert_eq!(w.adj(1).iter().map(|e| e.weight()).sum::<u32>(), 0);
+        let w: SparseWeightedGraph<f64> = to_weighted(&g, 1.5);
+        assert_eq!(w.e(), 2);
+        assert_eq!(w.adj(2)[0].weight(), 1.5);
+    }
+
+    #[test]
+    fn builder() {
+        assert_eq!(density(4, 6, false), 1.0);
+        assert_eq!(density(4, 6, true), 0.5);
+        assert_eq!(density(1, 0, false), 0.0);
+
+        // 完全图使用邻接矩阵
+        let mut builder = GraphBuilder::new(5, false);
+        for i in 0..5 {
+            for j in i + 1..5 {
+                builder.add_unweighted_edge(i, j);
+            }
+        }
+        let g = builder.build();
+        assert!(matches!(g, AutoGraph::Dense(_)));
+        assert_eq!(g.e(), 10);
+        assert!(g.has_edge(4, 0));
+
+        // 路径使用邻接表
+        let mut builder = GraphBuilder::new(20, true);
+        for i in 0..19 {
+            builder.add_edge(i, i + 1, i as u32);
+        }
+        let g = builder.build_weighted();
+        assert!(matches!(g, AutoWeightedGraph::Sparse(_)));
+        assert_eq!(g.adj(3)[0].weight(), 3);
+        assert!(!g.has_edge(1, 0));
+
+        // 调整阈值
+        let mut builder = GraphBuilder::new(20, true).threshold(0.0);
+        builder.add_edge(0, 1, 1.0);
+        assert!(matches!(
+            builder.build_weighted(),
+            AutoWeightedGraph::Dense(_)
+        ));
+    }
+}
@@ -1,40 +1,63 @@
 use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
-// 有权边
-#[derive(Clone)]
+// 有权边, 相等和哈希比较 (a, b, weight), a -> b 与 b -> a 是不同的边
+// 无向图中需要忽略方向时使用 Undirected, 按照权值比较时使用 ByWeight
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Edge<T> {
     a: usize,
     b: usize,
     weight: T,
 }
 
-impl<T> PartialEq for Edge<T>
-where
-    T: PartialEq,
-{
+// 忽略方向的边, a-b 和 b-a 相等且哈希值相同
+#[derive(Clone, Debug)]
+pub struct Undirected<T>(pub Edge<T>);
+
+impl<T> Undirected<T> {
+    fn key(&self) -> (usize, usize, &T) {
+        let e = &self.0;
+        (e.a.min(e.b), e.a.max(e.b), &e.weight)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Undirected<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.weight == other.weight
+        self.key() == other.key()
     }
 }
 
-impl<T> Eq for Edge<T> where T: PartialEq {}
+impl<T: Eq> Eq for Undirected<T> {}
 
-impl<T> PartialOrd for Edge<T>
-where
-    T: PartialEq + PartialOrd,
-{
+impl<T: Hash> Hash for Undirected<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+// 按照权值比较的边, 用于排序和优先队列
+// 只有权值实现了 Ord 才能放入 BinaryHeap, 浮点数需要先包装成全序的类型
+#[derive(Clone, Debug)]
+pub struct ByWeight<T>(pub Edge<T>);
+
+impl<T: PartialEq> PartialEq for ByWeight<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.weight == other.0.weight
+    }
+}
+
+impl<T: Eq> Eq for ByWeight<T> {}
+
+impl<T: PartialOrd> PartialOrd for ByWeight<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.weight.partial_cmp(&other.weight)
+        self.0.weight.partial_cmp(&other.0.weight)
     }
 }
 
-impl<T> Ord for Edge<T>
-where
-    T: PartialEq + PartialOrd + Eq,
-{
+impl<T: Ord> Ord for ByWeight<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.weight.partial_cmp(&other.weight).unwrap()
+        self.0.weight.cmp(&other.0.weight)
     }
 }
 
@@ -75,44 +98,99 @@ where
             return self.a;
         }
     }
+
+    // 按照权值比较两条边, 可以用于 sort_by
+    pub fn cmp_weight(&self, other: &Self) -> Ordering
+    where
+        T: Ord,
+    {
+        self.weight.cmp(&other.weight)
+    }
+
+    // 端点按照从小到大排列的边
+    pub fn normalized(&self) -> Self {
+        Self::new(self.a.min(self.b), self.a.max(self.b), self.weight())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashSet};
 
     #[test]
     fn edges_ordering() {
         // float实现了partial_cmp
-        let e1 = Edge::new(0, 1, 1.1);
-        let e2 = Edge::new(0, 1, 1.1);
-        let e3 = Edge::new(1, 2, 1.2);
-        let e4 = Edge::new(2, 3, 0.3);
+        let e1 = ByWeight(Edge::new(0, 1, 1.1));
+        let e2 = ByWeight(Edge::new(0, 1, 1.1));
+        let e3 = ByWeight(Edge::new(1, 2, 1.2));
+        let e4 = ByWeight(Edge::new(2, 3, 0.3));
 
         assert_eq!(e1.partial_cmp(&e2).unwrap(), Ordering::Equal);
         assert_eq!(e1.partial_cmp(&e3).unwrap(), Ordering::Less);
         assert_eq!(e1.partial_cmp(&e4).unwrap(), Ordering::Greater);
 
-        assert_eq!(e1 == e2, true);
-        assert_eq!(e1 <= e2, true);
-        assert_eq!(e1 < e2, false);
-        assert_eq!(e1 > e4, true);
-        assert_eq!(e1 >= e4, true);
+        assert!(e1 == e2);
+        assert!(e1 <= e2);
+        assert!(e1 >= e2);
+        assert!(e1 > e4);
+        assert!(e1 >= e4);
+
+        // NaN 与任何权值都无法比较
+        let nan = ByWeight(Edge::new(0, 1, f64::NAN));
+        assert_eq!(nan.partial_cmp(&e1), None);
 
         // i32实现了cmp
         let e1 = Edge::new(0, 1, 1);
-        let e2 = Edge::new(0, 1, 1);
+        let e2 = Edge::new(5, 6, 1);
         let e3 = Edge::new(1, 2, 2);
         let e4 = Edge::new(2, 3, -3);
 
-        assert_eq!(e1.cmp(&e2), Ordering::Equal);
-        assert_eq!(e1.cmp(&e3), Ordering::Less);
-        assert_eq!(e1.cmp(&e4), Ordering::Greater);
+        assert_eq!(e1.cmp_weight(&e2), Ordering::Equal);
+        assert_eq!(e1.cmp_weight(&e3), Ordering::Less);
+        assert_eq!(e1.cmp_weight(&e4), Ordering::Greater);
 
-        assert_eq!(e1 == e2, true);
-        assert_eq!(e1 <= e2, true);
-        assert_eq!(e1 < e2, false);
-        assert_eq!(e1 > e4, true);
-        assert_eq!(e1 >= e4, true);
+        assert!(ByWeight(e1.clone()) == ByWeight(e2.clone()));
+        assert!(ByWeight(e1.clone()) > ByWeight(e4.clone()));
+
+        let mut heap = BinaryHeap::new();
+        for e in [e1, e2, e3, e4] {
+            heap.push(Reverse(ByWeight(e)));
+        }
+        let Reverse(ByWeight(min)) = heap.pop().unwrap();
+        assert_eq!(min, Edge::new(2, 3, -3));
+    }
+
+    #[test]
+    fn edges_equality() {
+        // 权值相同但端点不同的边不相等
+        assert_ne!(Edge::new(0, 1, 5), Edge::new(3, 4, 5));
+        assert_ne!(Edge::new(0, 1, 5), Edge::new(0, 1, 6));
+        assert_ne!(Edge::new(0, 1, 5), Edge::new(1, 0, 5));
+        assert_eq!(Edge::new(0, 1, 5), Edge::new(0, 1, 5));
+
+        let set: HashSet<Edge<i32>> = [Edge::new(0, 1, 5), Edge::new(3, 4, 5), Edge::new(0, 1, 5)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+
+        // 忽略方向
+        assert_eq!(
+            Undirected(Edge::new(0, 1, 5)),
+            Undirected(Edge::new(1, 0, 5))
+        );
+        assert_ne!(
+            Undirected(Edge::new(0, 1, 5)),
+            Undirected(Edge::new(1, 0, 6))
+        );
+        let set: HashSet<Undirected<i32>> =
+            [Edge::new(0, 1, 5), Edge::new(1, 0, 5), Edge::new(2, 2, 1)]
+                .into_iter()
+                .map(Undirected)
+                .collect();
+        assert_eq!(set.len(), 2);
+
+        assert_eq!(Edge::new(3, 1, 2.5).normalized(), Edge::new(1, 3, 2.5));
     }
 }
@@ -1,15 +1,20 @@
 use super::{
-    components, path, prim, readgraph, DenseGraph, DenseWeightedGraph, Graph, SparseGraph,
-    SparseWeightedGraph, WeightedGraph,
+    centrality, coloring, components, connectivity, convert, dial, dijkstra, edge, euler,
+    hungarian, isomorphism, ksp, matching, mincut, path, prim, readgraph, stats, transform, weight,
+    zero_one, DenseGraph, DenseWeightedGraph, Edge, Graph, SparseGraph, SparseWeightedGraph,
+    WeightedGraph,
 };
-use rand::{self, Rng};
+use crate::util::random;
+use rand::Rng;
+use std::collections::HashSet;
 use std::path::Path;
+use std::time::Instant;
 
 fn graph_basic() {
     let n = 20;
     let m = 100;
 
-    let mut rng = rand::thread_rng();
+    let mut rng = random::rng();
 
     // Sparse Graph
     let mut g1 = SparseGraph::new(n, false);
@@ -54,7 +59,7 @@ fn weighted_graph_basic() {
     let n = 20;
     let m = 100;
 
-    let mut rng = rand::thread_rng();
+    let mut rng = random::rng();
 
     // Sparse Weighted Graph
     let mut g1 = SparseWeightedGraph::new(n, false);
@@ -212,12 +217,12 @@ fn unweighted_graph_path_bfs() {
 // lazy prim
 fn lazy_prim_mst() {
     let filename = Path::new("./src/files/graph/test3.txt");
-    let mut g: SparseWeightedGraph<prim::Float> = SparseWeightedGraph::new(8, false);
+    let mut g: SparseWeightedGraph<weight::OrderedF64> = SparseWeightedGraph::new(8, false);
     readgraph::read_weighted_graph(&mut g, filename).unwrap();
 
     let mut mst = prim::LazyPrimMST::new(&g);
 
-    // assert_eq!(mst.result(), Some(prim::Float(2.3499999999999996)));
+    // assert_eq!(mst.result(), Some(weight::OrderedF64::new(2.3499999999999996)));
     // println!("{:?}", mst.mst_edges());
     for edge in mst.mst_edges().iter() {
         println!("{}", edge);
@@ -225,6 +230,434 @@ fn lazy_prim_mst() {
     println!("{:?}", mst.result());
 }
 
+// 边的相等和按权值比较
+fn edge_equality() {
+    let mut g: SparseWeightedGraph<u32> = SparseWeightedGraph::new(4, false);
+    g.add_edge(0, 1, 5);
+    g.add_edge(1, 2, 3);
+    g.add_edge(2, 3, 5);
+    g.add_edge(3, 0, 1);
+
+    // 无向图中每条边在两个端点各出现一次, 忽略方向后去重
+    let edges: HashSet<edge::Undirected<u32>> = (0..g.v())
+        .flat_map(|v| g.adj(v).into_iter().cloned().map(edge::Undirected))
+        .collect();
+    println!("distinct edges: {}, e = {}", edges.len(), g.e());
+
+    let mut edges: Vec<Edge<u32>> = edges.into_iter().map(|e| e.0.normalized()).collect();
+    edges.sort_by(|a, b| a.cmp_weight(b).then(a.v().cmp(&b.v())));
+    for edge in edges.iter() {
+        println!("{}", edge);
+    }
+}
+
+// dijkstra
+fn dijkstra_shortest_path() {
+    let filename = Path::new("./src/files/graph/test3.txt");
+    let mut g: SparseWeightedGraph<weight::OrderedF64> = SparseWeightedGraph::new(8, false);
+    readgraph::read_weighted_graph(&mut g, filename).unwrap();
+
+    let mut sp = dijkstra::Dijkstra::new(&g, 0);
+    for w in 1..g.v() {
+        print!("0 -> {}, length {:?}: ", w, sp.length(w));
+        sp.show_path(w);
+    }
+
+    let mut g: DenseWeightedGraph<u32> = DenseWeightedGraph::new(4, true);
+    g.add_edge(0, 1, 3);
+    g.add_edge(1, 2, 4);
+    g.add_edge(0, 2, 9);
+    let mut sp = dijkstra::Dijkstra::new(&g, 0);
+    println!("distances: {:?}", sp.distances());
+}
+
+// 增量连通性
+fn incremental_connectivity() {
+    let filename = Path::new("./src/files/graph/test1.txt");
+    let mut g = SparseGraph::new(13, false);
+    readgraph::read(&mut g, filename).unwrap();
+
+    // 逐条加入边, 每次加入之后查询连通分量个数
+    let mut ic = connectivity::IncrementalConnectivity::new(g.v());
+    for v in 0..g.v() {
+        for w in g.adj(v) {
+            if v < w && ic.add_edge(v, w) {
+                println!(
+                    "add {}-{}, components: {}, size of {}: {}",
+                    v,
+                    w,
+                    ic.count(),
+                    v,
+                    ic.component_size(v)
+                );
+            }
+        }
+    }
+    println!("0 and 6 connected: {}", ic.is_connected(0, 6));
+    println!("0 and 9 connected: {}", ic.is_connected(0, 9));
+    println!("ids: {:?}", ic.snapshot());
+    println!(
+        "from graph: {:?}",
+        connectivity::IncrementalConnectivity::from_graph(&g).snapshot()
+    );
+    println!("Component ids: {:?}", components::Component::new(&g).ids());
+}
+
+// 随机生成有向图, 边的权值在 [0, max_weight] 之间
+fn random_integer_weighted_graph(n: usize, m: usize, max_weight: u32) -> SparseWeightedGraph<u32> {
+    let mut rng = random::rng();
+    let mut g = SparseWeightedGraph::new(n, true);
+    for _ in 0..m {
+        g.add_edge(
+            rng.gen_range(0, n),
+            rng.gen_range(0, n),
+            rng.gen_range(0, max_weight + 1),
+        );
+    }
+    g
+}
+
+// 0-1 BFS 和 Dial 算法, 与 Dijkstra 比较运行时间
+fn integer_weight_shortest_path() {
+    let (n, m) = (100000, 500000);
+
+    let g = random_integer_weighted_graph(n, m, 1);
+    let now = Instant::now();
+    let mut bfs = zero_one::ZeroOneBfs::new(&g, 0);
+    let bfs_len: Vec<Option<u32>> = (0..n).map(|v| bfs.length(v)).collect();
+    println!(
+        "0-1 BFS, V = {}, E = {}, {} µs",
+        n,
+        m,
+        now.elapsed().as_micros()
+    );
+
+    let now = Instant::now();
+    let mut dial = dial::Dial::new(&g, 0);
+    let dial_len: Vec<Option<u32>> = (0..n).map(|v| dial.length(v)).collect();
+    println!("Dial, weights 0-1, {} µs", now.elapsed().as_micros());
+
+    let now = Instant::now();
+    let mut sp = dijkstra::Dijkstra::new(&g, 0);
+    let dijkstra_len: Vec<Option<u32>> = (0..n).map(|v| sp.length(v)).collect();
+    println!("Dijkstra, weights 0-1, {} µs", now.elapsed().as_micros());
+    assert!(bfs_len == dijkstra_len && dial_len == dijkstra_len);
+
+    let g = random_integer_weighted_graph(n, m, 10);
+    let now = Instant::now();
+    let mut dial = dial::Dial::new(&g, 0);
+    let dial_len: Vec<Option<u32>> = (0..n).map(|v| dial.length(v)).collect();
+    println!("Dial, weights 0-10, {} µs", now.elapsed().as_micros());
+
+    let now = Instant::now();
+    let mut sp = dijkstra::Dijkstra::new(&g, 0);
+    let dijkstra_len: Vec<Option<u32>> = (0..n).map(|v| sp.length(v)).collect();
+    println!("Dijkstra, weights 0-10, {} µs", now.elapsed().as_micros());
+    assert_eq!(dial_len, dijkstra_len);
+
+    let mut path = Vec::new();
+    if dial.has_path(n - 1) {
+        dial.path(n - 1, &mut path);
+        println!("0 -> {}: {:?}", n - 1, path);
+    }
+    if bfs.has_path(n - 1) {
+        path.clear();
+        bfs.path(n - 1, &mut path);
+        println!(
+            "0 -> {} with 0-1 weights: {} vertices on the path",
+            n - 1,
+            path.len()
+        );
+    }
+}
+
+// 前k条最短路径
+fn k_shortest_paths() {
+    let filename = Path::new("./src/files/graph/test3.txt");
+    let mut g: SparseWeightedGraph<weight::OrderedF64> = SparseWeightedGraph::new(8, false);
+    readgraph::read_weighted_graph(&mut g, filename).unwrap();
+    println!("test3.txt, 5 shortest paths from 0 to 6:");
+    for (path, cost) in ksp::k_shortest_paths(&g, 0, 6, 5) {
+        println!("{:?}, length {:.2}", path, cost);
+    }
+
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, false);
+    readgraph::read(&mut g, filename).unwrap();
+    println!("test2.txt, 5 shortest paths from 0 to 3:");
+    for (path, len) in ksp::k_shortest_paths_unweighted(&g, 0, 3, 5) {
+        println!("{:?}, length {}", path, len);
+    }
+}
+
+// 图同构和子图同构
+fn graph_isomorphism() {
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, false);
+    readgraph::read(&mut g, filename).unwrap();
+
+    let reversed: SparseGraph = transform::reverse(&g);
+    println!(
+        "test2.txt degree sequence: {:?}",
+        isomorphism::degree_sequence(&g)
+    );
+    println!("test2.txt WL hash: {:x}", isomorphism::wl_hash(&g));
+    println!(
+        "test2.txt is isomorphic to its reverse: {}",
+        isomorphism::is_isomorphic(&g, &reversed)
+    );
+
+    let mut triangle = SparseGraph::new(3, false);
+    triangle.add_edge(0, 1);
+    triangle.add_edge(1, 2);
+    triangle.add_edge(2, 0);
+    let vf2 = isomorphism::Vf2::new(&triangle, &g, isomorphism::Mode::InducedSubgraph);
+    println!("triangles in test2.txt: {:?}", vf2.find_all(10));
+}
+
+// 二分图最大匹配
+fn bipartite_matching() {
+    // 工人 0..4, 任务 4..8
+    let mut g = SparseGraph::new(8, false);
+    g.add_edge(0, 4);
+    g.add_edge(0, 5);
+    g.add_edge(1, 4);
+    g.add_edge(2, 5);
+    g.add_edge(2, 6);
+    g.add_edge(3, 6);
+    g.add_edge(3, 7);
+
+    match matching::HopcroftKarp::new(&g) {
+        Ok(mut hk) => {
+            println!("Hopcroft-Karp matching size: {}", hk.size());
+            println!("matching: {:?}", hk.matching());
+            println!("minimum vertex cover: {:?}", hk.vertex_cover());
+        }
+        Err(e) => println!("{}", e),
+    }
+
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, false);
+    readgraph::read(&mut g, filename).unwrap();
+    if let Err(e) = matching::HopcroftKarp::new(&g) {
+        println!("test2.txt: {}", e);
+    }
+}
+
+// 最小费用分配
+fn hungarian_assignment() {
+    let cost = vec![vec![9, 2, 7, 8], vec![6, 4, 3, 7], vec![5, 8, 1, 8]];
+    let mut h = hungarian::Hungarian::from_matrix(&cost);
+    println!("Hungarian assignment: {:?}", h.assignment());
+    println!("total cost: {:?}", h.result());
+
+    // 左侧 0,1,2 右侧 3,4,5
+    let mut g: DenseWeightedGraph<weight::OrderedF32> = DenseWeightedGraph::new(6, false);
+    g.add_edge(0, 3, weight::OrderedF32::new(0.35));
+    g.add_edge(0, 4, weight::OrderedF32::new(0.16));
+    g.add_edge(1, 3, weight::OrderedF32::new(0.28));
+    g.add_edge(1, 5, weight::OrderedF32::new(0.58));
+    g.add_edge(2, 4, weight::OrderedF32::new(0.19));
+    g.add_edge(2, 5, weight::OrderedF32::new(0.40));
+    let mut h = hungarian::Hungarian::from_graph(&g, 3);
+    println!("Hungarian assignment: {:?}", h.assignment());
+    println!("total cost: {:?}", h.result());
+}
+
+// 欧拉路径
+fn euler_path() {
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, false);
+    readgraph::read(&mut g, filename).unwrap();
+    let mut euler = euler::Eulerian::new(&g);
+    println!(
+        "test2.txt, has euler path: {}, has euler circuit: {}",
+        euler.has_path(),
+        euler.has_circuit()
+    );
+    if euler.has_path() {
+        euler.show_path();
+    }
+
+    let mut g = SparseGraph::new(4, true);
+    g.add_edge(0, 1);
+    g.add_edge(1, 2);
+    g.add_edge(2, 0);
+    g.add_edge(2, 3);
+    g.add_edge(3, 3);
+    let mut euler = euler::Eulerian::new(&g);
+    println!("Euler path in directed graph:");
+    euler.show_path();
+}
+
+// 全局最小割
+fn stoer_wagner_min_cut() {
+    let filename = Path::new("./src/files/graph/test3.txt");
+    let mut g: SparseWeightedGraph<weight::OrderedF64> = SparseWeightedGraph::new(8, false);
+    readgraph::read_weighted_graph(&mut g, filename).unwrap();
+
+    let mut sw = mincut::StoerWagner::new(&g);
+    println!("test3.txt, min cut weight: {:?}", sw.result());
+    println!("partition: {:?}", sw.partition());
+}
+
+// 图的着色
+fn graph_coloring() {
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, false);
+    readgraph::read(&mut g, filename).unwrap();
+
+    let order: Vec<usize> = (0..g.v()).collect();
+    let greedy = coloring::greedy(&g, &order);
+    let welsh_powell = coloring::welsh_powell(&g);
+    let dsatur = coloring::dsatur(&g);
+    assert!(coloring::is_valid(&g, dsatur.colors()));
+    println!("test2.txt, greedy: {} colors", greedy.count());
+    println!("test2.txt, Welsh-Powell: {} colors", welsh_powell.count());
+    println!("test2.txt, DSatur: {} colors", dsatur.count());
+    for v in 0..g.v() {
+        println!("vertex {}: color {}", v, dsatur.color(v));
+    }
+}
+
+// 中心性
+fn graph_centrality() {
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, true);
+    readgraph::read(&mut g, filename).unwrap();
+
+    let config = centrality::PageRankConfig::default();
+    println!(
+        "test2.txt as digraph, PageRank: {:?}",
+        centrality::pagerank(&g, &config)
+    );
+    let config = centrality::PageRankConfig {
+        dangling: centrality::Dangling::SelfLoop,
+        ..config
+    };
+    println!(
+        "PageRank, dangling self loop: {:?}",
+        centrality::pagerank(&g, &config)
+    );
+    println!("in degree: {:?}", centrality::in_degree_centrality(&g));
+    println!("out degree: {:?}", centrality::out_degree_centrality(&g));
+
+    let mut g = SparseGraph::new(7, false);
+    readgraph::read(&mut g, filename).unwrap();
+    println!("test2.txt, degree: {:?}", centrality::degree_centrality(&g));
+    println!("closeness: {:?}", centrality::closeness_centrality(&g));
+    println!("betweenness: {:?}", centrality::betweenness_centrality(&g));
+
+    let filename = Path::new("./src/files/graph/test3.txt");
+    let mut g: SparseWeightedGraph<weight::OrderedF64> = SparseWeightedGraph::new(8, false);
+    readgraph::read_weighted_graph(&mut g, filename).unwrap();
+    println!(
+        "test3.txt, closeness: {:?}",
+        centrality::weighted_closeness_centrality(&g)
+    );
+    println!(
+        "test3.txt, betweenness: {:?}",
+        centrality::weighted_betweenness_centrality(&g)
+    );
+}
+
+// 图的统计信息
+fn graph_stats() {
+    let filename = Path::new("./src/files/graph/test1.txt");
+    let mut g = SparseGraph::new(13, false);
+    readgraph::read(&mut g, filename).unwrap();
+    println!("test1.txt stats:");
+    stats::GraphStats::new(&g).show();
+
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = DenseGraph::new(7, true);
+    readgraph::read(&mut g, filename).unwrap();
+    println!("test2.txt as digraph stats:");
+    stats::GraphStats::new(&g).show();
+
+    let filename = Path::new("./src/files/graph/test3.txt");
+    let mut g: SparseWeightedGraph<f64> = SparseWeightedGraph::new(8, false);
+    readgraph::read_weighted_graph(&mut g, filename).unwrap();
+    println!("test3.txt stats:");
+    stats::GraphStats::from_weighted(&g).show();
+}
+
+// 图的变换
+fn graph_transform() {
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, true);
+    readgraph::read(&mut g, filename).unwrap();
+
+    println!("test2.txt as digraph reversed:");
+    let r: SparseGraph = transform::reverse(&g);
+    r.show();
+
+    println!("induced subgraph of [0, 1, 2, 5]:");
+    let sub: DenseGraph = transform::induced_subgraph(&g, &[0, 1, 2, 5]);
+    sub.show();
+
+    println!("complement:");
+    let c: SparseGraph = transform::complement(&g);
+    c.show();
+
+    println!("disjoint union with its reverse:");
+    let u: SparseGraph = transform::disjoint_union(&g, &r);
+    u.show();
+
+    let (l, edges): (SparseGraph, _) = transform::line_graph(&g);
+    println!("line graph, vertices are edges {:?}:", edges);
+    l.show();
+
+    let filename = Path::new("./src/files/graph/test3.txt");
+    let mut g: SparseWeightedGraph<f64> = SparseWeightedGraph::new(8, false);
+    readgraph::read_weighted_graph(&mut g, filename).unwrap();
+    let r: DenseWeightedGraph<f64> = transform::reverse_weighted(&g);
+    let sub: SparseWeightedGraph<f64> = transform::induced_subgraph_weighted(&r, &[0, 2, 7]);
+    println!("test3.txt induced subgraph of [0, 2, 7]:");
+    sub.show();
+    let u: SparseWeightedGraph<f64> = transform::disjoint_union_weighted(&sub, &sub);
+    println!("disjoint union of the subgraph with itself:");
+    u.show();
+}
+
+// 图的表示方式转换
+fn graph_convert() {
+    let filename = Path::new("./src/files/graph/test2.txt");
+    let mut g = SparseGraph::new(7, false);
+    readgraph::read(&mut g, filename).unwrap();
+    println!("test2.txt as DenseGraph:");
+    DenseGraph::from(&g).show();
+
+    let w: SparseWeightedGraph<u32> = convert::to_weighted(&g, 1);
+    println!("test2.txt as SparseWeightedGraph:");
+    w.show();
+    let dense = DenseWeightedGraph::from(&w);
+    let g: SparseGraph = convert::to_unweighted(&dense);
+    println!("back to SparseGraph, e = {}", g.e());
+
+    let mut builder = convert::GraphBuilder::new(4, false);
+    for i in 0..4 {
+        for j in i + 1..4 {
+            builder.add_unweighted_edge(i, j);
+        }
+    }
+    match builder.build() {
+        convert::AutoGraph::Dense(g) => println!("complete graph uses DenseGraph, e = {}", g.e()),
+        convert::AutoGraph::Sparse(g) => println!("complete graph uses SparseGraph, e = {}", g.e()),
+    }
+
+    let mut builder = convert::GraphBuilder::new(100, false).threshold(0.1);
+    for i in 0..99 {
+        builder.add_edge(i, i + 1, 0.5);
+    }
+    let g = builder.build_weighted();
+    println!(
+        "path graph density {:.4}, dense = {}",
+        convert::density(g.v(), g.e(), g.is_directed()),
+        matches!(g, convert::AutoWeightedGraph::Dense(_))
+    );
+}
+
 pub fn run() {
     // 测试图结构
     graph_basic();
@@ -236,6 +669,9 @@ pub fn run() {
     // 测试连通分量
     graph_connected_components();
 
+    // 测试增量连通性
+    incremental_connectivity();
+
     // 测试寻路算法
     unweighted_graph_path_dfs();
 
@@ -247,4 +683,46 @@ pub fn run() {
 
     // 测试最小生成树
     lazy_prim_mst();
+
+    // 测试边的比较
+    edge_equality();
+
+    // 测试 Dijkstra 最短路径
+    dijkstra_shortest_path();
+
+    // 测试 0-1 BFS 和 Dial 算法
+    integer_weight_shortest_path();
+
+    // 测试前k条最短路径
+    k_shortest_paths();
+
+    // 测试图同构和子图同构
+    graph_isomorphism();
+
+    // 测试二分图最大匹配
+    bipartite_matching();
+
+    // 测试最小费用分配
+    hungarian_assignment();
+
+    // 测试欧拉路径
+    euler_path();
+
+    // 测试全局最小割
+    stoer_wagner_min_cut();
+
+    // 测试图的着色
+    graph_coloring();
+
+    // 测试中心性
+    graph_centrality();
+
+    // 测试图的统计信息
+    graph_stats();
+
+    // 测试图的变换
+    graph_transform();
+
+    // 测试图的表示方式转换
+    graph_convert();
 }
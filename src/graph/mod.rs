@@ -1,13 +1,29 @@
 pub mod algo;
+pub mod convert;
 pub mod edge;
 pub mod examples;
 pub mod readgraph;
 pub mod unweighted;
+pub mod weight;
 pub mod weighted;
 
+use algo::centrality;
+use algo::coloring;
 use algo::components;
+use algo::connectivity;
+use algo::dial;
+use algo::dijkstra;
+use algo::euler;
+use algo::hungarian;
+use algo::isomorphism;
+use algo::ksp;
+use algo::matching;
+use algo::mincut;
 use algo::path;
 use algo::prim;
+use algo::stats;
+use algo::transform;
+use algo::zero_one;
 use edge::Edge;
 use unweighted::{DenseGraph, Graph, SparseGraph};
 use weighted::{DenseWeightedGraph, SparseWeightedGraph, WeightedGraph};
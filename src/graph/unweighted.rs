@@ -2,6 +2,7 @@ pub trait Graph {
     fn new(n: usize, directed: bool) -> Self;
     fn v(&self) -> usize;
     fn e(&self) -> usize;
+    fn is_directed(&self) -> bool;
     fn add_edge(&mut self, v: usize, w: usize);
     fn has_edge(&self, v: usize, w: usize) -> bool;
     fn adj(&self, v: usize) -> Vec<usize>;
@@ -35,6 +36,11 @@ impl Graph for DenseGraph {
         self.m
     }
 
+    // 返回是否为有向图
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
     fn add_edge(&mut self, v: usize, w: usize) {
         assert!(v < self.n && w < self.n);
 
@@ -98,6 +104,11 @@ impl Graph for SparseGraph {
         self.m
     }
 
+    // 返回是否为有向图
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
     fn add_edge(&mut self, v: usize, w: usize) {
         assert!(v < self.n && w < self.n);
 
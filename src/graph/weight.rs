@@ -0,0 +1,295 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::num::ParseFloatError;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+// 边的权值, 要求全序并且支持加减法
+// 整数直接实现, 浮点数使用不允许 NaN 的 OrderedF64 和 OrderedF32
+pub trait Weight:
+    Copy + Ord + Debug + Display + Add<Output = Self> + Sub<Output = Self> + AddAssign
+{
+    // 加法单位元, 即空路径的权值
+    fn zero() -> Self;
+    // 不可达时的距离, 整数为最大值
+    fn infinity() -> Self;
+    // 转换为 f64, 用于计算平均值等统计量
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! integer_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn infinity() -> Self {
+                    <$t>::MAX
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+integer_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// 非负整数权值, 用于 0-1 BFS 和 Dial 算法这类按照距离分桶的算法
+pub trait IntegerWeight: Weight {
+    fn one() -> Self;
+    // 作为桶的下标, 超出 usize 的范围时 panic
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! unsigned_weight {
+    ($($t:ty),*) => {
+        $(
+            impl IntegerWeight for $t {
+                fn one() -> Self {
+                    1
+                }
+
+                fn to_usize(self) -> usize {
+                    usize::try_from(self).expect("weight does not fit in usize")
+                }
+            }
+        )*
+    };
+}
+
+unsigned_weight!(u8, u16, u32, u64, u128, usize);
+
+// 解析浮点数权值失败
+#[derive(Debug, PartialEq)]
+pub enum ParseWeightError {
+    Float(ParseFloatError),
+    NaN,
+}
+
+impl Display for ParseWeightError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseWeightError::Float(e) => write!(f, "{}", e),
+            ParseWeightError::NaN => write!(f, "weight is NaN"),
+        }
+    }
+}
+
+// 不允许 NaN 的浮点数, 构造或者运算结果为 NaN 时 panic
+// -0.0 统一为 0.0, 保证相等的值哈希值也相同
+macro_rules! ordered_float {
+    ($name:ident, $t:ty) => {
+        #[derive(Copy, Clone, Default)]
+        pub struct $name($t);
+
+        impl $name {
+            pub fn new(value: $t) -> Self {
+                match Self::try_new(value) {
+                    Some(v) => v,
+                    None => panic!("{} can not be NaN", stringify!($name)),
+                }
+            }
+
+            // value 为 NaN 时返回 None
+            pub fn try_new(value: $t) -> Option<Self> {
+                if value.is_nan() {
+                    None
+                } else {
+                    Some(Self(value + 0.0))
+                }
+            }
+
+            pub fn value(self) -> $t {
+                self.0
+            }
+        }
+
+        impl From<$name> for $t {
+            fn from(v: $name) -> $t {
+                v.0
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        // 不存在 NaN, total_cmp 与 == 的结果一致
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.to_bits().hash(state);
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                Debug::fmt(&self.0, f)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseWeightError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let value = <$t>::from_str(s).map_err(ParseWeightError::Float)?;
+                Self::try_new(value).ok_or(ParseWeightError::NaN)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self::new(-self.0)
+            }
+        }
+
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self(0.0), |a, b| a + b)
+            }
+        }
+
+        impl Weight for $name {
+            fn zero() -> Self {
+                Self(0.0)
+            }
+
+            fn infinity() -> Self {
+                Self(<$t>::INFINITY)
+            }
+
+            fn to_f64(self) -> f64 {
+                self.0 as f64
+            }
+        }
+
+        ordered_float!(@op $name, Add, add, AddAssign, add_assign);
+        ordered_float!(@op $name, Sub, sub, SubAssign, sub_assign);
+        ordered_float!(@op $name, Mul, mul, MulAssign, mul_assign);
+        ordered_float!(@op $name, Div, div, DivAssign, div_assign);
+        ordered_float!(@op $name, Rem, rem, RemAssign, rem_assign);
+    };
+    (@op $name:ident, $op:ident, $f:ident, $op_assign:ident, $f_assign:ident) => {
+        impl $op for $name {
+            type Output = Self;
+            fn $f(self, other: Self) -> Self {
+                Self::new(self.0.$f(other.0))
+            }
+        }
+
+        impl $op_assign for $name {
+            fn $f_assign(&mut self, other: Self) {
+                *self = self.$f(other);
+            }
+        }
+    };
+}
+
+ordered_float!(OrderedF64, f64);
+ordered_float!(OrderedF32, f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BinaryHeap, HashSet};
+
+    fn sum<W: Weight>(values: &[W]) -> W {
+        let mut total = W::zero();
+        for &v in values.iter() {
+            total += v;
+        }
+        total
+    }
+
+    #[test]
+    fn integer() {
+        assert_eq!(sum(&[1u8, 2, 3]), 6);
+        assert_eq!(sum(&[-1i64, 2, -3]), -2);
+        assert_eq!(u32::infinity(), u32::MAX);
+        assert_eq!(i16::zero(), 0);
+        assert_eq!(7usize.to_f64(), 7.0);
+        assert_eq!(u64::one(), 1);
+        assert_eq!(300u16.to_usize(), 300);
+    }
+
+    #[test]
+    fn ordered_float() {
+        let a = OrderedF64::new(1.5);
+        let b = OrderedF64::new(0.25);
+        assert_eq!(a + b, OrderedF64::new(1.75));
+        assert_eq!(a - b, OrderedF64::new(1.25));
+        assert_eq!(a * b, OrderedF64::new(0.375));
+        assert_eq!(a / b, OrderedF64::new(6.0));
+        assert_eq!(a % b, OrderedF64::new(0.0));
+        assert_eq!(-a, OrderedF64::new(-1.5));
+        assert_eq!(sum(&[a, b]), OrderedF64::new(1.75));
+        assert_eq!([a, b].into_iter().sum::<OrderedF64>().value(), 1.75);
+        assert!(b < a && a < OrderedF64::infinity());
+
+        let mut c = a;
+        c -= b;
+        c *= OrderedF64::new(2.0);
+        assert_eq!(f64::from(c), 2.5);
+
+        // -0.0 和 0.0 相等, 哈希值也相同
+        let set: HashSet<OrderedF64> = [OrderedF64::new(-0.0), OrderedF64::new(0.0)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 1);
+
+        let mut heap: BinaryHeap<OrderedF32> = [3.0, -1.0, 2.5]
+            .iter()
+            .map(|&v| OrderedF32::new(v))
+            .collect();
+        assert_eq!(heap.pop(), Some(OrderedF32::new(3.0)));
+        assert_eq!(OrderedF32::new(0.5).to_f64(), 0.5);
+    }
+
+    #[test]
+    fn reject_nan() {
+        assert!(OrderedF64::try_new(f64::NAN).is_none());
+        assert_eq!("0.35".parse::<OrderedF64>(), Ok(OrderedF64::new(0.35)));
+        assert_eq!("NaN".parse::<OrderedF32>(), Err(ParseWeightError::NaN));
+        assert!(matches!(
+            "abc".parse::<OrderedF64>(),
+            Err(ParseWeightError::Float(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "OrderedF64 can not be NaN")]
+    fn nan_arithmetic() {
+        let inf = OrderedF64::infinity();
+        let _ = inf - inf;
+    }
+}
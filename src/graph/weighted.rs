@@ -14,6 +14,7 @@ pub trait WeightedGraph<T> {
     fn new(n: usize, directed: bool) -> Self;
     fn v(&self) -> usize;
     fn e(&self) -> usize;
+    fn is_directed(&self) -> bool;
     fn add_edge(&mut self, v: usize, w: usize, weight: T);
     fn has_edge(&self, v: usize, w: usize) -> bool;
     fn adj(&self, v: usize) -> Vec<&Edge<T>>;
@@ -43,6 +44,11 @@ where
         self.m
     }
 
+    // 返回是否为有向图
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
     fn add_edge(&mut self, v: usize, w: usize, weight: T) {
         assert!(v < self.n && w < self.n);
 
@@ -109,8 +115,8 @@ where
     T: Copy + fmt::Display,
 {
     fn new(n: usize, directed: bool) -> Self {
-        // g初始化为n*n的布尔矩阵, g[i][j]为None, 表示没有任和边
-        let g = vec![vec![None; n]; n];
+        // g初始化为n个空的邻接表
+        let g = vec![vec![]; n];
         let m = 0;
         Self { n, m, directed, g }
     }
@@ -125,6 +131,11 @@ where
         self.m
     }
 
+    // 返回是否为有向图
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
     fn add_edge(&mut self, v: usize, w: usize, weight: T) {
         assert!(v < self.n && w < self.n);
         self.g[v].push(Some(Edge::new(v, w, weight)));
@@ -172,12 +183,13 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::edge::ByWeight;
 
     #[test]
     fn edge() {
         // float
-        let a = Edge::new(0, 1, 1.1);
-        let b = Edge::new(0, 1, 1.2);
+        let a = ByWeight(Edge::new(0, 1, 1.1));
+        let b = ByWeight(Edge::new(0, 1, 1.2));
         assert_eq!(a < b, true);
         assert_eq!(a <= b, true);
         assert_eq!(a > b, false);
@@ -186,8 +198,8 @@ mod tests {
         assert_eq!(a != b, true);
 
         // string
-        let a = Edge::new(0, 1, "abc");
-        let b = Edge::new(0, 1, "def");
+        let a = ByWeight(Edge::new(0, 1, "abc"));
+        let b = ByWeight(Edge::new(0, 1, "def"));
         assert_eq!(a < b, true);
         assert_eq!(a <= b, true);
         assert_eq!(a > b, false);
@@ -196,8 +208,8 @@ mod tests {
         assert_eq!(a != b, true);
 
         // i32
-        let a = Edge::new(0, 1, -3);
-        let b = Edge::new(0, 1, 3);
+        let a = ByWeight(Edge::new(0, 1, -3));
+        let b = ByWeight(Edge::new(0, 1, 3));
         assert_eq!(a < b, true);
         assert_eq!(a <= b, true);
         assert_eq!(a > b, false);
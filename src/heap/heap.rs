@@ -1,4 +1,5 @@
-use rand::{self, Rng};
+use crate::util::random;
+use rand::Rng;
 use std::cmp::min;
 
 #[allow(dead_code)]
@@ -253,7 +254,7 @@ fn put_branch_in_line(line: &mut Vec<u8>, index_cur_level: usize, cur_tree_width
 fn make_sure_ordered() {
     let capacity = 100;
     let mut max_heap = MaxHeap::with_capacity(capacity);
-    let mut rng = rand::thread_rng();
+    let mut rng = random::rng();
 
     for _ in 0..capacity {
         max_heap.insert(rng.gen_range(0, capacity));
@@ -275,7 +276,7 @@ pub fn run() {
     // print heap
     let capacity = 10;
     let mut max_heap = MaxHeap::with_capacity(capacity);
-    let mut rng = rand::thread_rng();
+    let mut rng = random::rng();
 
     for _ in 0..capacity {
         max_heap.insert(rng.gen_range(0, capacity));
@@ -340,52 +341,56 @@ mod tests {
 
     #[test]
     fn max_heap() {
-        let capacity = 100;
-        let mut rng = rand::thread_rng();
-        let mut max_heap = MaxHeap::with_capacity(capacity);
+        random::reproducible(|| {
+            let capacity = 100;
+            let mut rng = random::rng();
+            let mut max_heap = MaxHeap::with_capacity(capacity);
 
-        for _ in 0..100 {
-            max_heap.insert(rng.gen_range(0, capacity));
-        }
+            for _ in 0..100 {
+                max_heap.insert(rng.gen_range(0, capacity));
+            }
 
-        let mut ordered = Vec::new();
-        while let Some(v) = max_heap.extract_max() {
-            ordered.push(v);
-            println!("{}", v);
-        }
+            let mut ordered = Vec::new();
+            while let Some(v) = max_heap.extract_max() {
+                ordered.push(v);
+                println!("{}", v);
+            }
 
-        for i in 1..capacity {
-            assert!(ordered[i - 1] >= ordered[i]);
-        }
+            for i in 1..capacity {
+                assert!(ordered[i - 1] >= ordered[i]);
+            }
+        });
     }
 
     #[test]
     fn heapify() {
-        let capacity = 100;
-        let mut rng = rand::thread_rng();
-
-        // 构建随机数组
-        let mut vector = Vec::new();
-        for _ in 0..capacity {
-            vector.push(rng.gen_range(0, capacity));
-        }
+        random::reproducible(|| {
+            let capacity = 100;
+            let mut rng = random::rng();
+
+            // 构建随机数组
+            let mut vector = Vec::new();
+            for _ in 0..capacity {
+                vector.push(rng.gen_range(0, capacity));
+            }
 
-        // heapify
-        let mut max_heap = MaxHeap::with_heapify(&vector);
+            // heapify
+            let mut max_heap = MaxHeap::with_heapify(&vector);
 
-        let mut ordered = Vec::new();
-        // for i in 0..100 {
-        //     ordered.push(max_heap.extract_max());
-        //     print!("{} ", ordered[i].unwrap());
-        // }
+            let mut ordered = Vec::new();
+            // for i in 0..100 {
+            //     ordered.push(max_heap.extract_max());
+            //     print!("{} ", ordered[i].unwrap());
+            // }
 
-        while let Some(v) = max_heap.extract_max() {
-            ordered.push(v);
-            println!("{}", v);
-        }
+            while let Some(v) = max_heap.extract_max() {
+                ordered.push(v);
+                println!("{}", v);
+            }
 
-        for i in 1..capacity {
-            assert!(ordered[i - 1] >= ordered[i]);
-        }
+            for i in 1..capacity {
+                assert!(ordered[i - 1] >= ordered[i]);
+            }
+        });
     }
 }
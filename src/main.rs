@@ -8,15 +8,13 @@ mod uf;
 mod util;
 
 fn main() {
-    sorting::selection::run();
-    sorting::insertion::run();
-    sorting::bubble::run();
-    sorting::merge::run();
-    sorting::heap::run();
-    sorting::quick::run();
-    tree::bst::run();
-    map::hashmap::run();
-    uf::unionfind::run();
-    graph::examples::run();
-    heap::heap::run();
+    println!("seed: {}", util::random::seed());
+    util::random::reproducible(|| {
+        sorting::sorter::run();
+        tree::bst::run();
+        map::hashmap::run();
+        uf::unionfind::run();
+        graph::examples::run();
+        heap::heap::run();
+    });
 }
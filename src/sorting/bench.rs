@@ -0,0 +1,538 @@
+use crate::sorting::sorter::{self, Complexity, SortFn, Sorter};
+use crate::util;
+use crate::util::generate;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// 排序算法的基准测试, 对每个算法在不同规模和不同分布的输入上重复运行
+// 先运行 warmup 次不计时, 再运行 trials 次统计最小值、中位数和 p95
+
+// 输入数据的分布
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    // [1, n] 之间的随机数
+    Random,
+    // 有序数组中随机交换 swap_times 对元素
+    NearlyOrdered { swap_times: i32 },
+    // [0, high] 之间的随机数, 有大量重复元素
+    Duplicates { high: i32 },
+    // 有序数组
+    Sorted,
+    // 逆序数组
+    Reversed,
+    // 所有元素相同
+    AllEqual,
+    // 只有 k 个不同的值
+    FewUnique { k: usize },
+    // 由 teeth 段递增序列组成
+    Sawtooth { teeth: usize },
+    // 先递增后递减
+    OrganPipe,
+    // 均值为 n / 2 的正态分布
+    Gaussian { std_dev: u32 },
+    // 取值为 [1, k] 的 Zipf 分布, s = 1
+    Zipf { k: usize },
+    // 针对 algorithm 构造的快速排序对抗输入
+    Killer { algorithm: &'static str },
+}
+
+impl Distribution {
+    pub fn name(&self) -> String {
+        match self {
+            Distribution::Random => "random".to_string(),
+            Distribution::NearlyOrdered { swap_times } => {
+                format!("nearly ordered ({})", swap_times)
+            }
+            Distribution::Duplicates { high } => format!("duplicates [0,{}]", high),
+            Distribution::Sorted => "sorted".to_string(),
+            Distribution::Reversed => "reversed".to_string(),
+            Distribution::AllEqual => "all equal".to_string(),
+            Distribution::FewUnique { k } => format!("few unique ({})", k),
+            Distribution::Sawtooth { teeth } => format!("sawtooth ({})", teeth),
+            Distribution::OrganPipe => "organ pipe".to_string(),
+            Distribution::Gaussian { std_dev } => format!("gaussian (σ={})", std_dev),
+            Distribution::Zipf { k } => format!("zipf ({})", k),
+            Distribution::Killer { algorithm } => format!("killer ({})", algorithm),
+        }
+    }
+
+    // 带随机性的分布以 n 作为 seed, 相同的规模总是生成相同的输入
+    pub fn generate(&self, n: usize) -> Vec<i32> {
+        let seed = n as u64;
+        let mut rng = StdRng::seed_from_u64(seed);
+        match *self {
+            Distribution::Random => {
+                util::generate_random_array_with_rng(n as i32, 1, n as i32, &mut rng)
+            }
+            Distribution::NearlyOrdered { swap_times } => {
+                generate::nearly_sorted(n, swap_times as usize, seed)
+            }
+            Distribution::Duplicates { high } => {
+                util::generate_random_array_with_rng(n as i32, 0, high, &mut rng)
+            }
+            Distribution::Sorted => generate::sorted(n),
+            Distribution::Reversed => generate::reversed(n),
+            Distribution::AllEqual => generate::all_equal(n, 0),
+            Distribution::FewUnique { k } => generate::few_unique(n, k, seed),
+            Distribution::Sawtooth { teeth } => generate::sawtooth(n, teeth),
+            Distribution::OrganPipe => generate::organ_pipe(n),
+            Distribution::Gaussian { std_dev } => {
+                generate::gaussian(n, n as f64 / 2.0, std_dev as f64, seed)
+            }
+            Distribution::Zipf { k } => generate::zipf(n, k, 1.0, seed),
+            Distribution::Killer { algorithm } => {
+                let sorter = sorter::find::<usize>(algorithm)
+                    .unwrap_or_else(|| panic!("unknown algorithm: {}", algorithm));
+                // Sorter 要求 Fn + Sync 的比较函数, 对抗算法的比较函数有状态, 放在 Mutex 中
+                generate::quicksort_killer(n, |arr, compare| {
+                    let compare = Mutex::new(compare);
+                    sorter.sort_by(arr, &|a, b| (compare.lock().unwrap())(a, b))
+                })
+            }
+        }
+    }
+}
+
+// 输出格式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Markdown,
+}
+
+// 一个算法在一种输入上的测试结果
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub algorithm: String,
+    pub distribution: String,
+    pub n: usize,
+    pub trials: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Measurement {
+    // 由每次运行的耗时计算统计量, times 不能为空
+    pub fn new(algorithm: &str, distribution: &str, n: usize, mut times: Vec<Duration>) -> Self {
+        assert!(!times.is_empty());
+        times.sort_unstable();
+        let len = times.len();
+        let median = if len % 2 == 1 {
+            times[len / 2]
+        } else {
+            (times[len / 2 - 1] + times[len / 2]) / 2
+        };
+        // nearest-rank 方法, 第 ceil(0.95 * len) 个值
+        let rank = (len * 95).div_ceil(100);
+
+        Self {
+            algorithm: algorithm.to_string(),
+            distribution: distribution.to_string(),
+            n,
+            trials: len,
+            min: times[0],
+            median,
+            p95: times[rank - 1],
+        }
+    }
+}
+
+fn micros(d: Duration) -> f64 {
+    d.as_nanos() as f64 / 1000.0
+}
+
+// CSV 中包含逗号或者引号的字段需要用引号括起来
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(ret, "\\u{:04x}", c as u32).unwrap(),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    rows: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn new(rows: Vec<Measurement>) -> Self {
+        Self { rows }
+    }
+
+    pub fn rows(&self) -> &[Measurement] {
+        &self.rows
+    }
+
+    // 合并另一次测试的结果
+    pub fn extend(&mut self, other: Report) {
+        self.rows.extend(other.rows);
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        let mut ret = String::new();
+        match format {
+            Format::Csv => {
+                ret.push_str("algorithm,distribution,n,trials,min_us,median_us,p95_us\n");
+                for r in self.rows.iter() {
+                    writeln!(
+                        ret,
+                        "{},{},{},{},{:.3},{:.3},{:.3}",
+                        csv_field(&r.algorithm),
+                        csv_field(&r.distribution),
+                        r.n,
+                        r.trials,
+                        micros(r.min),
+                        micros(r.median),
+                        micros(r.p95)
+                    )
+                    .unwrap();
+                }
+            }
+            Format::Json => {
+                ret.push('[');
+                for (i, r) in self.rows.iter().enumerate() {
+                    if i > 0 {
+                        ret.push(',');
+                    }
+                    write!(
+                        ret,
+                        "\n  {{\"algorithm\": {}, \"distribution\": {}, \"n\": {}, \"trials\": {}, \
+                         \"min_us\": {:.3}, \"median_us\": {:.3}, \"p95_us\": {:.3}}}",
+                        json_string(&r.algorithm),
+                        json_string(&r.distribution),
+                        r.n,
+                        r.trials,
+                        micros(r.min),
+                        micros(r.median),
+                        micros(r.p95)
+                    )
+                    .unwrap();
+                }
+                if !self.rows.is_empty() {
+                    ret.push('\n');
+                }
+                ret.push_str("]\n");
+            }
+            Format::Markdown => {
+                ret.push_str(
+                    "| algorithm | distribution | n | trials | min (µs) | median (µs) | p95 (µs) |\n",
+                );
+                ret.push_str("|---|---|---:|---:|---:|---:|---:|\n");
+                for r in self.rows.iter() {
+                    writeln!(
+                        ret,
+                        "| {} | {} | {} | {} | {:.3} | {:.3} | {:.3} |",
+                        r.algorithm.replace('|', "\\|"),
+                        r.distribution.replace('|', "\\|"),
+                        r.n,
+                        r.trials,
+                        micros(r.min),
+                        micros(r.median),
+                        micros(r.p95)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        ret
+    }
+
+    // 按照 format 写入文件, 目录不存在时自动创建
+    pub fn save<P: AsRef<Path>>(&self, path: P, format: Format) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.render(format))
+    }
+}
+
+pub struct Bench {
+    sizes: Vec<usize>,
+    distributions: Vec<Distribution>,
+    warmup: usize,
+    trials: usize,
+    max_quadratic: usize,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bench {
+    pub fn new() -> Self {
+        Self {
+            sizes: vec![100, 1000, 10000],
+            distributions: vec![Distribution::Random],
+            warmup: 1,
+            trials: 5,
+            max_quadratic: 10000,
+        }
+    }
+
+    pub fn sizes(mut self, sizes: &[usize]) -> Self {
+        self.sizes = sizes.to_vec();
+        self
+    }
+
+    pub fn distributions(mut self, distributions: &[Distribution]) -> Self {
+        self.distributions = distributions.to_vec();
+        self
+    }
+
+    pub fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    pub fn trials(mut self, trials: usize) -> Self {
+        assert!(trials > 0);
+        self.trials = trials;
+        self
+    }
+
+    // 平均复杂度为 O(n^2) 的算法只测试 n <= max_quadratic 的输入
+    pub fn max_quadratic(mut self, max_quadratic: usize) -> Self {
+        self.max_quadratic = max_quadratic;
+        self
+    }
+
+    // 同一种分布和规模下所有算法使用相同的输入, 每次运行前重新复制
+    // 排序结果不正确时 panic
+    pub fn run(&self, sorters: &[Box<dyn Sorter<i32>>]) -> Report {
+        let mut rows = Vec::new();
+        for distribution in self.distributions.iter() {
+            for &n in self.sizes.iter() {
+                let input = distribution.generate(n);
+                for sorter in sorters.iter() {
+                    if sorter.average_case() == Complexity::Quadratic && n > self.max_quadratic {
+                        continue;
+                    }
+                    rows.push(
+                        self.measure(sorter.name(), &distribution.name(), &input, |arr| {
+                            sorter.sort(arr)
+                        }),
+                    );
+                }
+            }
+        }
+        Report::new(rows)
+    }
+
+    // 在给定的输入上测试一组排序函数, 输入可以是任意类型, 例如浮点数和字符串
+    // 用于不在 Sorter 中的算法, 例如 counting sort, radix sort 和 bucket sort
+    // 不使用 sizes 和 distributions, input 的描述由 distribution 给出
+    pub fn run_on<T>(&self, distribution: &str, input: &[T], sorts: &[(&str, SortFn<T>)]) -> Report
+    where
+        T: Clone + PartialOrd,
+    {
+        let rows = sorts
+            .iter()
+            .map(|&(name, sort)| self.measure(name, distribution, input, sort))
+            .collect();
+        Report::new(rows)
+    }
+
+    // 先运行 warmup 次不计时, 再运行 trials 次计时, 排序结果不正确时 panic
+    fn measure<T, F>(&self, name: &str, distribution: &str, input: &[T], sort: F) -> Measurement
+    where
+        T: Clone + PartialOrd,
+        F: Fn(&mut [T]),
+    {
+        for _ in 0..self.warmup {
+            sort(&mut input.to_vec());
+        }
+        let mut times = Vec::with_capacity(self.trials);
+        for _ in 0..self.trials {
+            let mut arr = input.to_vec();
+            let now = Instant::now();
+            sort(&mut arr);
+            times.push(now.elapsed());
+            assert!(util::is_sorted(&arr), "{} failed", name);
+        }
+        Measurement::new(name, distribution, input.len(), times)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting::{bucket, counting, radix, sorter};
+
+    fn ms(v: &[u64]) -> Vec<Duration> {
+        v.iter().map(|&x| Duration::from_millis(x)).collect()
+    }
+
+    #[test]
+    fn statistics() {
+        let m = Measurement::new("a", "random", 10, ms(&[5, 1, 4, 2, 3]));
+        assert_eq!(m.min, Duration::from_millis(1));
+        assert_eq!(m.median, Duration::from_millis(3));
+        assert_eq!(m.p95, Duration::from_millis(5));
+        assert_eq!(m.trials, 5);
+
+        let m = Measurement::new("a", "random", 10, ms(&[4, 1, 3, 2]));
+        assert_eq!(m.median, Duration::from_micros(2500));
+
+        // 20 次运行时 p95 为第 19 小的值
+        let times: Vec<u64> = (1..=20).collect();
+        let m = Measurement::new("a", "random", 10, ms(&times));
+        assert_eq!(m.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn render() {
+        let report = Report::new(vec![
+            Measurement::new("quick sort_v4", "random", 100, ms(&[1, 2, 3])),
+            Measurement::new("a,\"b\"", "duplicates [0,10]", 10, ms(&[1])),
+        ]);
+
+        let csv = report.render(Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "algorithm,distribution,n,trials,min_us,median_us,p95_us"
+        );
+        assert_eq!(
+            lines[1],
+            "quick sort_v4,random,100,3,1000.000,2000.000,3000.000"
+        );
+        assert_eq!(
+            lines[2],
+            "\"a,\"\"b\"\"\",\"duplicates [0,10]\",10,1,1000.000,1000.000,1000.000"
+        );
+
+        let json = report.render(Format::Json);
+        assert!(json.starts_with("[\n  {\"algorithm\": \"quick sort_v4\""));
+        assert!(json.contains("\"algorithm\": \"a,\\\"b\\\"\""));
+        assert!(json.contains("\"median_us\": 2000.000"));
+        assert_eq!(Report::default().render(Format::Json), "[]\n");
+
+        let md = report.render(Format::Markdown);
+        assert_eq!(md.lines().count(), 4);
+        assert!(
+            md.contains("| quick sort_v4 | random | 100 | 3 | 1000.000 | 2000.000 | 3000.000 |")
+        );
+    }
+
+    #[test]
+    fn run() {
+        let sorters = vec![
+            sorter::find("selection sort").unwrap(),
+            sorter::find("quick sort_v4").unwrap(),
+        ];
+        let report = Bench::new()
+            .sizes(&[10, 200])
+            .distributions(&[
+                Distribution::Random,
+                Distribution::NearlyOrdered { swap_times: 3 },
+            ])
+            .warmup(0)
+            .trials(3)
+            .max_quadratic(100)
+            .run(&sorters);
+
+        // selection sort 跳过 n = 200 的输入
+        assert_eq!(report.rows().len(), 6);
+        assert!(report
+            .rows()
+            .iter()
+            .all(|r| r.trials == 3 && r.min <= r.median && r.median <= r.p95));
+        assert!(!report
+            .rows()
+            .iter()
+            .any(|r| r.algorithm == "selection sort" && r.n == 200));
+    }
+
+    #[test]
+    fn distributions() {
+        let killer = Distribution::Killer {
+            algorithm: "quick sort_v1",
+        };
+        assert_eq!(killer.name(), "killer (quick sort_v1)");
+        let mut input = killer.generate(100);
+        input.sort_unstable();
+        assert_eq!(input, (0..100).collect::<Vec<i32>>());
+
+        assert_eq!(Distribution::Reversed.generate(3), vec![2, 1, 0]);
+        assert_eq!(
+            Distribution::Zipf { k: 5 }.generate(50),
+            Distribution::Zipf { k: 5 }.generate(50)
+        );
+        let sorters = sorter::registry::<i32>();
+        let report = Bench::new()
+            .sizes(&[50])
+            .distributions(&[
+                Distribution::AllEqual,
+                Distribution::FewUnique { k: 3 },
+                Distribution::Sawtooth { teeth: 4 },
+                Distribution::OrganPipe,
+                Distribution::Gaussian { std_dev: 5 },
+                killer,
+            ])
+            .warmup(0)
+            .trials(1)
+            .run(&sorters);
+        assert_eq!(report.rows().len(), 6 * sorters.len());
+    }
+
+    #[test]
+    fn run_on() {
+        let bench = Bench::new().warmup(0).trials(2);
+        let floats: Vec<f64> = (0..100).map(|i| ((i * 37) % 100) as f64 / 100.0).collect();
+        let report = bench.run_on("floats", &floats, &[("bucket sort", bucket::sort)]);
+        assert_eq!(report.rows().len(), 1);
+        let r = &report.rows()[0];
+        assert_eq!(
+            (r.algorithm.as_str(), r.distribution.as_str()),
+            ("bucket sort", "floats")
+        );
+        assert_eq!((r.n, r.trials), (100, 2));
+
+        let input: Vec<u32> = vec![3, u32::MAX, 0, 7];
+        let report = bench.run_on(
+            "full range",
+            &input,
+            &[
+                ("counting sort", counting::sort),
+                ("lsd radix sort", radix::lsd_sort),
+            ],
+        );
+        assert_eq!(report.rows().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "reverse failed")]
+    fn run_on_checks_result() {
+        Bench::new().warmup(0).trials(1).run_on(
+            "random",
+            &[1, 3, 2],
+            &[("reverse", |arr: &mut [i32]| arr.reverse())],
+        );
+    }
+}
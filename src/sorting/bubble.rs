@@ -1,8 +1,13 @@
-use crate::sorting::insertion;
-use crate::sorting::selection;
-use crate::util;
+use std::cmp::Ordering;
 
 pub fn sort_v1<T: Ord>(arr: &mut [T]) {
+    sort_v1_by(arr, T::cmp);
+}
+
+pub fn sort_v1_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len();
 
     if len == 0 {
@@ -11,81 +16,19 @@ pub fn sort_v1<T: Ord>(arr: &mut [T]) {
 
     for i in 0..len - 1 {
         for j in 0..len - 1 - i {
-            if arr[j] > arr[j + 1] {
+            if compare(&arr[j], &arr[j + 1]) == Ordering::Greater {
                 arr.swap(j, j + 1)
             }
         }
     }
 }
 
-pub fn run() {
-    println!("Test for random array in 1-n .");
-
-    let n = 100;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("bubble sort_v1", sort_v1, &mut arr4);
-
-    let n = 1000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("bubble sort_v1", sort_v1, &mut arr4);
-
-    let n = 10000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("bubble sort_v1", sort_v1, &mut arr4);
-
-    let swap_times = 10;
-    println!(
-        "Test for nearly ordered array, swap_times = {} .",
-        swap_times
-    );
-
-    let n = 100;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("bubble sort_v1", sort_v1, &mut arr4);
-
-    let n = 1000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("bubble sort_v1", sort_v1, &mut arr4);
-
-    let n = 10000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("bubble sort_v1", sort_v1, &mut arr4);
+pub fn sort_v1_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v1_by(arr, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
@@ -119,4 +62,16 @@ mod tests {
         sort_v1(&mut res);
         assert_eq!(res, vec!['a', 'b', 'c', 'd']);
     }
+
+    #[test]
+    fn by_and_key() {
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort_v1_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, vec![8, 7, 5, 4, 1]);
+
+        // 冒泡排序是稳定的, key 相同的元素保持原来的顺序
+        let mut res = vec![(3, 'a'), (1, 'b'), (3, 'c'), (1, 'd')];
+        sort_v1_by_key(&mut res, |p| p.0);
+        assert_eq!(res, vec![(1, 'b'), (1, 'd'), (3, 'a'), (3, 'c')]);
+    }
 }
@@ -0,0 +1,98 @@
+use std::cmp::Ordering;
+
+// bucket sort, 适用于在 [0, 1) 之间均匀分布的浮点数
+// 把 [0, 1) 平均分为 n 个桶, x 放入第 floor(x * n) 个桶, 每个桶使用 insertion sort
+// 均匀分布时每个桶的元素个数的期望为 O(1), 平均复杂度为 O(n)
+pub fn sort<T>(arr: &mut [T])
+where
+    T: Copy + PartialOrd + Into<f64>,
+{
+    let n = arr.len();
+    if n < 2 {
+        return;
+    }
+
+    let index = |x: T| {
+        let v: f64 = x.into();
+        assert!((0.0..1.0).contains(&v), "{} is not in [0, 1)", v);
+        ((v * n as f64) as usize).min(n - 1)
+    };
+
+    // 所有的桶放在同一个数组中, 第 b 个桶为 arr[start[b]..start[b+1]]
+    let mut start = vec![0; n + 1];
+    for &x in arr.iter() {
+        start[index(x) + 1] += 1;
+    }
+    for b in 0..n {
+        start[b + 1] += start[b];
+    }
+
+    let aux = arr.to_vec();
+    let mut next = start.clone();
+    for &x in aux.iter() {
+        let b = index(x);
+        arr[next[b]] = x;
+        next[b] += 1;
+    }
+
+    for b in 0..n {
+        insertion_sort(&mut arr[start[b]..start[b + 1]]);
+    }
+}
+
+fn insertion_sort<T: Copy + PartialOrd>(arr: &mut [T]) {
+    for i in 1..arr.len() {
+        let e = arr[i];
+        let mut j = i;
+        while j > 0 && e.partial_cmp(&arr[j - 1]) == Some(Ordering::Less) {
+            arr[j] = arr[j - 1];
+            j -= 1;
+        }
+        arr[j] = e;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{self, random};
+    use rand::Rng;
+
+    fn ordered<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+        a.partial_cmp(b).unwrap()
+    }
+
+    #[test]
+    fn floats() {
+        let mut res = vec![0.78, 0.17, 0.39, 0.26, 0.72, 0.94, 0.21, 0.12, 0.23, 0.68];
+        sort(&mut res);
+        assert_eq!(
+            res,
+            vec![0.12, 0.17, 0.21, 0.23, 0.26, 0.39, 0.68, 0.72, 0.78, 0.94]
+        );
+
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for n in [0, 1, 2, 100, 10000] {
+                let mut res: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
+                sort(&mut res);
+                assert!(util::is_sorted_by(&res, ordered));
+
+                let mut res: Vec<f32> = (0..n).map(|_| rng.gen()).collect();
+                sort(&mut res);
+                assert!(util::is_sorted_by(&res, ordered));
+
+                // 不均匀的分布同样可以正确排序
+                let mut res: Vec<f64> = (0..n).map(|_| rng.gen::<f64>().powi(8)).collect();
+                sort(&mut res);
+                assert!(util::is_sorted_by(&res, ordered));
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        sort(&mut [0.5, 1.0]);
+    }
+}
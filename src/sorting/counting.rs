@@ -0,0 +1,141 @@
+use super::radix::{self, RadixKey};
+
+// counting sort, 统计每个 key 出现的次数, 再按照前缀和把元素放到对应的位置
+// 复杂度为 O(n + k), k 为 key 的取值范围, 适用于 k 与 n 相当的场景
+// 从左向右放置元素, 相同 key 的元素保持原来的顺序, 是稳定的
+
+// key 的取值范围不能超过 MAX_RANGE, 计数数组最多占用 2^24 个 usize, 即 128 MiB
+// 取值范围更大的整数应该使用 radix::lsd_sort
+const MAX_RANGE: u128 = 1 << 24;
+
+// 按照 key 排序, key 的取值为 [0, max_key], max_key 不能超过 MAX_RANGE - 1
+pub fn sort_by_key<T, F>(arr: &mut [T], mut key: F)
+where
+    T: Clone,
+    F: FnMut(&T) -> usize,
+{
+    if arr.len() < 2 {
+        return;
+    }
+
+    let keys: Vec<usize> = arr.iter().map(&mut key).collect();
+    let range = *keys.iter().max().unwrap() as u128 + 1;
+    assert!(
+        range <= MAX_RANGE,
+        "key range {} is too large for counting sort, use radix::lsd_sort instead",
+        range
+    );
+
+    // start[k] 为 key 等于 k 的第一个元素的位置
+    let mut start = vec![0; range as usize + 1];
+    for &k in keys.iter() {
+        start[k + 1] += 1;
+    }
+    for k in 0..range as usize {
+        start[k + 1] += start[k];
+    }
+
+    let aux = arr.to_vec();
+    for (x, &k) in aux.into_iter().zip(keys.iter()) {
+        arr[start[k]] = x;
+        start[k] += 1;
+    }
+}
+
+// 整数的 counting sort, 取值范围为 [min, max]
+// max - min 超过 MAX_RANGE 时计数数组过大, 改用同样稳定的 radix::lsd_sort
+pub fn sort<T: RadixKey>(arr: &mut [T]) {
+    let min = match arr.iter().min() {
+        Some(min) => min.bits(),
+        None => return,
+    };
+    let max = arr.iter().max().unwrap().bits();
+    if max - min >= MAX_RANGE {
+        radix::lsd_sort(arr);
+        return;
+    }
+    sort_by_key(arr, |x| (x.bits() - min) as usize);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{self, generate, random};
+    use rand::Rng;
+
+    #[test]
+    fn integer() {
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort(&mut res);
+        assert_eq!(res, vec![1, 4, 5, 7, 8]);
+
+        let mut res: Vec<i64> = vec![];
+        sort(&mut res);
+        assert!(res.is_empty());
+
+        let mut res = vec![i64::MAX, i64::MAX - 3, i64::MAX - 1];
+        sort(&mut res);
+        assert_eq!(res, vec![i64::MAX - 3, i64::MAX - 1, i64::MAX]);
+
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for n in [1, 2, 100, 5000] {
+                let arr: Vec<i16> = (0..n).map(|_| rng.gen()).collect();
+                let mut res = arr.clone();
+                sort(&mut res);
+                assert!(util::is_sorted(&res));
+
+                let arr: Vec<u8> = generate::few_unique(n, 3, rng.gen());
+                let mut res = arr.clone();
+                sort(&mut res);
+                assert!(util::is_sorted(&res));
+
+                let mut res = util::generate_random_array(n as i32, -50, 50);
+                sort(&mut res);
+                assert!(util::is_sorted(&res));
+            }
+        });
+    }
+
+    #[test]
+    fn stable() {
+        let mut res: Vec<(usize, usize)> = (0..1000).map(|i| ((i * 7) % 10, i)).collect();
+        sort_by_key(&mut res, |p| p.0);
+        for pair in res.windows(2) {
+            assert!(pair[0].0 < pair[1].0 || pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1);
+        }
+    }
+
+    #[test]
+    fn full_range() {
+        // 取值范围超过 MAX_RANGE 时使用 radix::lsd_sort, 不会分配巨大的计数数组
+        let mut res = vec![u32::MAX, 0, 7];
+        sort(&mut res);
+        assert_eq!(res, vec![0, 7, u32::MAX]);
+
+        let mut res = vec![0u64, u64::MAX, 1];
+        sort(&mut res);
+        assert_eq!(res, vec![0, 1, u64::MAX]);
+
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            let arr: Vec<i32> = (0..5000).map(|_| rng.gen()).collect();
+            let mut expected = arr.clone();
+            expected.sort();
+            let mut res = arr.clone();
+            sort(&mut res);
+            assert_eq!(res, expected);
+
+            let mut res: Vec<u32> = (0..5000).map(|_| rng.gen()).collect();
+            sort(&mut res);
+            assert!(util::is_sorted(&res));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "radix::lsd_sort")]
+    fn range_too_large() {
+        let mut res = vec![0u32, u32::MAX];
+        sort_by_key(&mut res, |&x| x as usize);
+    }
+}
@@ -1,44 +1,141 @@
 use crate::heap::MaxHeap;
-use crate::sorting::insertion;
-use crate::sorting::merge;
-use crate::sorting::selection;
-use crate::util;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+
+// 让 MaxHeap 使用自定义的比较函数, 所有元素共享同一个 compare
+struct ByCompare<'a, T, F> {
+    value: T,
+    compare: &'a RefCell<F>,
+}
+
+impl<'a, T: Clone, F> Clone for ByCompare<'a, T, F> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            compare: self.compare,
+        }
+    }
+}
+
+impl<'a, T, F> PartialEq for ByCompare<'a, T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, T, F> Eq for ByCompare<'a, T, F> where F: FnMut(&T, &T) -> Ordering {}
+
+impl<'a, T, F> PartialOrd for ByCompare<'a, T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T, F> Ord for ByCompare<'a, T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.compare.borrow_mut())(&self.value, &other.value)
+    }
+}
 
 pub fn sort_v1<T: Ord + Clone>(arr: &mut [T]) {
+    sort_v1_by(arr, T::cmp);
+}
+
+pub fn sort_v1_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len();
+    let compare = RefCell::new(compare);
     let mut heap = MaxHeap::with_capacity(len);
-    for i in 0..len {
-        heap.insert(arr[i].clone());
+    for v in arr.iter() {
+        heap.insert(ByCompare {
+            value: v.clone(),
+            compare: &compare,
+        });
     }
 
     for i in (0..len).rev() {
-        arr[i] = heap.extract_max().unwrap();
+        arr[i] = heap.extract_max().unwrap().value;
     }
 }
 
+pub fn sort_v1_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v1_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
 pub fn sort_v2<T: Ord + Clone>(arr: &mut [T]) {
+    sort_v2_by(arr, T::cmp);
+}
+
+pub fn sort_v2_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len();
-    let mut vector = arr.to_vec();
-    let mut heap = MaxHeap::with_heapify(&mut vector);
+    let compare = RefCell::new(compare);
+    let vector: Vec<ByCompare<T, F>> = arr
+        .iter()
+        .map(|v| ByCompare {
+            value: v.clone(),
+            compare: &compare,
+        })
+        .collect();
+    let mut heap = MaxHeap::with_heapify(&vector);
 
     for i in (0..len).rev() {
-        arr[i] = heap.extract_max().unwrap();
+        arr[i] = heap.extract_max().unwrap().value;
     }
 }
 
+pub fn sort_v2_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v2_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
 // heap sort in place
 pub fn sort_v3<T: Ord>(arr: &mut [T]) {
+    sort_v3_by(arr, T::cmp);
+}
+
+pub fn sort_v3_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
     // parent = index; left child = 2*index+1; right child = 2*index+2
-    fn shift_down<T: Ord>(arr: &mut [T], max_index: usize, index: usize) {
+    fn shift_down<T, F>(arr: &mut [T], max_index: usize, index: usize, compare: &mut F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
         let mut index = index;
         while 2 * index + 1 <= max_index {
             let mut child = 2 * index + 1;
             let right = child + 1;
-            if right <= max_index && arr[child] < arr[right] {
+            if right <= max_index && compare(&arr[child], &arr[right]) == Ordering::Less {
                 child = right;
             }
 
-            if arr[index] >= arr[child] {
+            if compare(&arr[index], &arr[child]) != Ordering::Less {
                 break;
             }
 
@@ -47,8 +144,8 @@ pub fn sort_v3<T: Ord>(arr: &mut [T]) {
         }
     }
 
-    // empty
-    if arr.len() == 0 {
+    // 空数组或者只有一个元素时已经有序
+    if arr.len() < 2 {
         return;
     }
 
@@ -57,7 +154,7 @@ pub fn sort_v3<T: Ord>(arr: &mut [T]) {
     // parent = (index-1)/2 => last_parent = (max_index-1)/2
     let max_index = arr.len() - 1;
     for index in (0..=(max_index - 1) / 2).rev() {
-        shift_down(arr, max_index, index);
+        shift_down(arr, max_index, index, &mut compare);
     }
 
     // heap sort in place
@@ -65,114 +162,16 @@ pub fn sort_v3<T: Ord>(arr: &mut [T]) {
     while end >= 1 {
         arr.swap(0, end);
         end -= 1;
-        shift_down(arr, end, 0);
+        shift_down(arr, end, 0, &mut compare);
     }
 }
 
-pub fn run() {
-    println!("Test for random array in 1-n .");
-
-    let n = 100;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", sort_v3, &mut arr7);
-
-    let n = 1000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", sort_v3, &mut arr7);
-
-    let n = 10000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", sort_v3, &mut arr7);
-
-    let swap_times = 10;
-    println!(
-        "Test for nearly ordered array, swap_times = {} .",
-        swap_times
-    );
-
-    let n = 100;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", sort_v3, &mut arr7);
-
-    let n = 1000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", sort_v3, &mut arr7);
-
-    let n = 10000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", sort_v3, &mut arr7);
+pub fn sort_v3_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v3_by(arr, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
@@ -238,4 +237,38 @@ mod tests {
         sort_v3(&mut res);
         assert_eq!(res, vec!['a', 'b', 'c', 'd']);
     }
+
+    #[test]
+    fn by_and_key() {
+        let expected: Vec<i32> = (0..50).rev().collect();
+        let mut res: Vec<i32> = (0..50).collect();
+        sort_v1_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, expected);
+        let mut res: Vec<i32> = (0..50).collect();
+        sort_v2_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, expected);
+        let mut res: Vec<i32> = (0..50).collect();
+        sort_v3_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, expected);
+
+        let words = vec!["pear", "fig", "banana", "kiwi"];
+        let mut res = words.clone();
+        sort_v1_by_key(&mut res, |w| w.len());
+        assert_eq!(
+            res.iter().map(|w| w.len()).collect::<Vec<_>>(),
+            vec![3, 4, 4, 6]
+        );
+        let mut res = words.clone();
+        sort_v2_by_key(&mut res, |w| w.len());
+        assert_eq!(
+            res.iter().map(|w| w.len()).collect::<Vec<_>>(),
+            vec![3, 4, 4, 6]
+        );
+        let mut res = words.clone();
+        sort_v3_by_key(&mut res, |w| w.len());
+        assert_eq!(
+            res.iter().map(|w| w.len()).collect::<Vec<_>>(),
+            vec![3, 4, 4, 6]
+        );
+    }
 }
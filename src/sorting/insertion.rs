@@ -1,14 +1,20 @@
-use crate::sorting::selection;
-use crate::util;
+use std::cmp::Ordering;
 
 pub fn sort_v1<T: Ord>(arr: &mut [T]) {
+    sort_v1_by(arr, T::cmp);
+}
+
+pub fn sort_v1_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len();
 
     for i in 1..len {
         // 在 arr[1..i] 之间寻找合适的插入位置
         for j in (1..i + 1).rev() {
             // 如果当前元素比前面的元素小刚交换位置
-            if arr[j] < arr[j - 1] {
+            if compare(&arr[j], &arr[j - 1]) == Ordering::Less {
                 arr.swap(j, j - 1);
             } else {
                 break;
@@ -17,8 +23,24 @@ pub fn sort_v1<T: Ord>(arr: &mut [T]) {
     }
 }
 
+pub fn sort_v1_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v1_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
 // 优化点：swap 操作转为赋值操作
 pub fn sort_v2<T: Ord + Clone>(arr: &mut [T]) {
+    sort_v2_by(arr, T::cmp);
+}
+
+pub fn sort_v2_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len();
 
     for i in 1..len {
@@ -27,7 +49,7 @@ pub fn sort_v2<T: Ord + Clone>(arr: &mut [T]) {
         let mut left: usize = i;
         for j in (1..i + 1).rev() {
             // 如果当前元素比前面的元素小刚交换位置
-            if e < arr[j - 1] {
+            if compare(&e, &arr[j - 1]) == Ordering::Less {
                 arr[j] = arr[j - 1].clone();
                 left -= 1;
             } else {
@@ -38,14 +60,31 @@ pub fn sort_v2<T: Ord + Clone>(arr: &mut [T]) {
     }
 }
 
+pub fn sort_v2_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v2_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
 pub fn sort_v3<T: Ord + Clone>(arr: &mut [T], start: usize, end: usize) {
+    sort_v3_by(arr, start, end, T::cmp);
+}
+
+pub fn sort_v3_by<T, F>(arr: &mut [T], start: usize, end: usize, mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     for i in (start + 1)..(end + 1) {
         let e = arr[i].clone();
         // 在 arr[start+1..i] 之间寻找合适的插入位置
         let mut left: usize = i;
         for j in ((start + 1)..(i + 1)).rev() {
             // 如果当前元素比前面的元素小刚交换位置
-            if e < arr[j - 1] {
+            if compare(&e, &arr[j - 1]) == Ordering::Less {
                 arr[j] = arr[j - 1].clone();
                 left -= 1;
             } else {
@@ -55,64 +94,14 @@ pub fn sort_v3<T: Ord + Clone>(arr: &mut [T], start: usize, end: usize) {
         arr[left] = e;
     }
 }
-// 简单测试sort的时间复杂度
-// 对不同数量的元素进行排序，观察元素数量和排序时间的关系
-pub fn run() {
-    println!("Test for random array in 1-n .");
-
-    let n = 100;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", sort_v2, &mut arr3);
-
-    let n = 1000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", sort_v2, &mut arr3);
-
-    let n = 10000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", sort_v2, &mut arr3);
-
-    let swap_times = 10;
-    println!(
-        "Test for nearly ordered array, swap_times = {} .",
-        swap_times
-    );
-
-    let n = 100;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", sort_v2, &mut arr3);
-
-    let n = 1000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", sort_v2, &mut arr3);
-
-    let n = 10000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", sort_v2, &mut arr3);
+
+pub fn sort_v3_by_key<T, K, F>(arr: &mut [T], start: usize, end: usize, mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v3_by(arr, start, end, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
@@ -180,4 +169,30 @@ mod tests {
         sort_v3(&mut res, 0, 3);
         assert_eq!(res, vec!['a', 'b', 'c', 'd']);
     }
+
+    #[test]
+    fn by_and_key() {
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort_v1_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, vec![8, 7, 5, 4, 1]);
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort_v2_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, vec![8, 7, 5, 4, 1]);
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort_v3_by(&mut res, 1, 3, |a, b| b.cmp(a));
+        assert_eq!(res, vec![4, 8, 5, 1, 7]);
+
+        // 插入排序是稳定的, key 相同的元素保持原来的顺序
+        let pairs = vec![(3, 'a'), (1, 'b'), (3, 'c'), (1, 'd')];
+        let expected = vec![(1, 'b'), (1, 'd'), (3, 'a'), (3, 'c')];
+        let mut res = pairs.clone();
+        sort_v1_by_key(&mut res, |p| p.0);
+        assert_eq!(res, expected);
+        let mut res = pairs.clone();
+        sort_v2_by_key(&mut res, |p| p.0);
+        assert_eq!(res, expected);
+        let mut res = pairs.clone();
+        sort_v3_by_key(&mut res, 0, 3, |p| p.0);
+        assert_eq!(res, expected);
+    }
 }
@@ -0,0 +1,151 @@
+use super::{heap, insertion, quick};
+use crate::util::random;
+use rand::Rng;
+use std::cmp::Ordering;
+
+// introsort: 使用 3-ways quick sort 排序
+// 递归深度超过 2*log2(n) 时说明 pivot 选择得不好, 对剩余部分改用 heap sort
+// 小规模数组使用 insertion sort, 最坏情况下的复杂度为 O(n log n)
+fn intro_sort<T, F, R>(
+    arr: &mut [T],
+    low: usize,
+    high: usize,
+    depth: usize,
+    compare: &mut F,
+    rng: &mut R,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    if low >= high {
+        return;
+    }
+
+    if high - low <= 15 {
+        insertion::sort_v3_by(arr, low, high, &mut *compare);
+        return;
+    }
+
+    if depth == 0 {
+        heap::sort_v3_by(&mut arr[low..=high], &mut *compare);
+        return;
+    }
+
+    let (lt, gt) = quick::partition_v4(arr, low, high, compare, rng);
+    if lt > 0 {
+        intro_sort(arr, low, lt - 1, depth - 1, compare, rng);
+    }
+    intro_sort(arr, gt, high, depth - 1, compare, rng);
+}
+
+// 递归深度的上限 2*log2(n)
+fn max_depth(n: usize) -> usize {
+    2 * n.max(1).ilog2() as usize
+}
+
+pub fn sort<T: Ord + Clone>(arr: &mut [T]) {
+    sort_by(arr, T::cmp);
+}
+
+pub fn sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_by_with_rng(arr, compare, &mut random::rng());
+}
+
+// 由 rng 选择 pivot, 相同的 rng 状态得到相同的比较序列
+pub fn sort_by_with_rng<T, F, R>(arr: &mut [T], mut compare: F, rng: &mut R)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    if !arr.is_empty() {
+        let high = arr.len() - 1;
+        intro_sort(arr, 0, high, max_depth(arr.len()), &mut compare, rng)
+    }
+}
+
+pub fn sort_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{self, generate};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn integer() {
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort(&mut res);
+        assert_eq!(res, vec![1, 4, 5, 7, 8]);
+
+        let mut res: Vec<i32> = vec![];
+        sort(&mut res);
+        assert!(res.is_empty());
+
+        random::reproducible(|| {
+            for n in [1, 2, 16, 17, 100, 1000] {
+                let mut res = util::generate_random_array(n, 0, n / 2);
+                sort(&mut res);
+                assert!(util::is_sorted(&res));
+            }
+        });
+    }
+
+    #[test]
+    fn by_and_key() {
+        let mut res: Vec<i32> = (0..100).collect();
+        sort_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, (0..100).rev().collect::<Vec<i32>>());
+
+        let mut res: Vec<i32> = (-50..50).collect();
+        sort_by_key(&mut res, |x| x.abs());
+        assert!(res.windows(2).all(|p| p[0].abs() <= p[1].abs()));
+    }
+
+    #[test]
+    fn killer() {
+        random::reproducible(|| {
+            let n = 2000;
+            let seed: u64 = random::rng().gen();
+            let count = |arr: &[usize]| {
+                let mut calls = 0;
+                let mut arr = arr.to_vec();
+                sort_by_with_rng(
+                    &mut arr,
+                    |a, b| {
+                        calls += 1;
+                        a.cmp(b)
+                    },
+                    &mut StdRng::seed_from_u64(seed),
+                );
+                assert!(util::is_sorted(&arr));
+                calls
+            };
+
+            // 对 quick sort_v4 来说是最坏情况的输入, introsort 仍然是 O(n log n)
+            let killer: Vec<usize> = generate::quicksort_killer(n, |arr, compare| {
+                sort_by_with_rng(arr, compare, &mut StdRng::seed_from_u64(seed))
+            });
+            let log_n = (n as f64).log2() as usize;
+            assert!(count(&killer) < 8 * n * log_n);
+
+            let v4_killer: Vec<usize> = generate::quicksort_killer(n, |arr, compare| {
+                quick::sort_v4_by_with_rng(arr, compare, &mut StdRng::seed_from_u64(seed))
+            });
+            assert!(count(&v4_killer) < 8 * n * log_n);
+        });
+    }
+}
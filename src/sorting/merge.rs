@@ -1,38 +1,68 @@
 use crate::sorting::insertion;
-use crate::sorting::selection;
-use crate::util;
+use std::cmp::Ordering;
 
 pub fn sort_v1<T: Ord + Clone>(arr: &mut [T]) {
+    sort_v1_by(arr, T::cmp);
+}
+
+pub fn sort_v1_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len();
     if len == 0 {
         return;
     }
-    merge_sort(arr, 0, len - 1);
+    // 所有的 merge 共用一个辅助数组
+    let mut aux = Vec::with_capacity(len);
+    merge_sort(arr, &mut aux, 0, len - 1, &mut compare);
 }
 
-fn merge_sort<T: Ord + Clone>(arr: &mut [T], start: usize, end: usize) {
+pub fn sort_v1_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v1_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
+fn merge_sort<T, F>(arr: &mut [T], aux: &mut Vec<T>, start: usize, end: usize, compare: &mut F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     // 小规模数据，使用 insertion sort
     if end - start <= 15 {
-        insertion::sort_v3(arr, start, end);
+        insertion::sort_v3_by(arr, start, end, &mut *compare);
         return;
     }
 
     let mid = start + (end - start) / 2;
 
     // 对左半部分进行排序
-    merge_sort(arr, start, mid);
+    merge_sort(arr, aux, start, mid, compare);
     // 对右半部分进行排序
-    merge_sort(arr, mid + 1, end);
+    merge_sort(arr, aux, mid + 1, end, compare);
     // 对两部分进行 merge
-    merge(arr, start, mid, end)
+    merge(arr, aux, start, mid, end, compare)
 }
 
-fn merge<T: Ord + Clone>(arr: &mut [T], start: usize, mid: usize, end: usize) {
-    // 分配辅助数组
-    let mut aux = Vec::with_capacity(end - start + 1);
-    for i in start..=end {
-        aux.push(arr[i].clone());
-    }
+pub(super) fn merge<T, F>(
+    arr: &mut [T],
+    aux: &mut Vec<T>,
+    start: usize,
+    mid: usize,
+    end: usize,
+    compare: &mut F,
+) where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    // 复制到辅助数组
+    aux.clear();
+    aux.extend_from_slice(&arr[start..=end]);
 
     let mut i = start;
     let mut j = mid + 1;
@@ -47,98 +77,88 @@ fn merge<T: Ord + Clone>(arr: &mut [T], start: usize, mid: usize, end: usize) {
             arr[k] = aux[i - start].clone();
             i += 1;
         }
-        // 左半部分当前元素 < 右半部分当前元素
-        else if aux[i - start] < aux[j - start] {
+        // 右半部分当前元素 < 左半部分当前元素
+        else if compare(&aux[j - start], &aux[i - start]) == Ordering::Less {
+            arr[k] = aux[j - start].clone();
+            j += 1;
+        }
+        // 左半部分当前元素 <= 右半部分当前元素, 相等时取左边的元素保证稳定
+        else {
             arr[k] = aux[i - start].clone();
             i += 1;
         }
-        // 左半部分当前元素 > 右半部分当前元素
-        else {
-            arr[k] = aux[j - start].clone();
-            j += 1;
+    }
+}
+
+// 自底向上的归并排序, 不使用递归
+// 先对每 16 个元素使用 insertion sort, 之后依次归并长度为 16, 32, 64, ... 的相邻部分
+pub fn sort_v2<T: Ord + Clone>(arr: &mut [T]) {
+    sort_v2_by(arr, T::cmp);
+}
+
+pub fn sort_v2_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    const RUN: usize = 16;
+    let len = arr.len();
+    for start in (0..len).step_by(RUN) {
+        insertion::sort_v3_by(arr, start, (start + RUN - 1).min(len - 1), &mut compare);
+    }
+
+    let mut aux = Vec::with_capacity(len);
+    let mut size = RUN;
+    while size < len {
+        for start in (0..len - size).step_by(2 * size) {
+            let mid = start + size - 1;
+            let end = (start + 2 * size - 1).min(len - 1);
+            // 两部分已经有序时不需要 merge
+            if compare(&arr[mid + 1], &arr[mid]) == Ordering::Less {
+                merge(arr, &mut aux, start, mid, end, &mut compare);
+            }
         }
+        size *= 2;
     }
 }
 
-pub fn run() {
-    println!("Test for random array in 1-n .");
-
-    let n = 100;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", sort_v1, &mut arr4);
-
-    let n = 1000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", sort_v1, &mut arr4);
-
-    let n = 10000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", sort_v1, &mut arr4);
-
-    let swap_times = 10;
-    println!(
-        "Test for nearly ordered array, swap_times = {} .",
-        swap_times
-    );
-
-    let n = 100;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", sort_v1, &mut arr4);
-
-    let n = 1000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", sort_v1, &mut arr4);
-
-    let n = 10000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    util::test_sort("selection sort", selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", sort_v1, &mut arr4);
+pub fn sort_v2_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v2_by(arr, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::{self, random};
 
     #[test]
     fn integer() {
         let mut res = vec![4, 1, 8, 5, 7];
         sort_v1(&mut res);
         assert_eq!(res, vec![1, 4, 5, 7, 8]);
+
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort_v2(&mut res);
+        assert_eq!(res, vec![1, 4, 5, 7, 8]);
+
+        random::reproducible(|| {
+            for n in [16, 17, 32, 33, 100, 1000] {
+                let arr = util::generate_random_array(n, 0, n / 3);
+                let mut expected = arr.clone();
+                expected.sort();
+                let mut res = arr.clone();
+                sort_v1(&mut res);
+                assert_eq!(res, expected);
+                let mut res = arr.clone();
+                sort_v2(&mut res);
+                assert_eq!(res, expected);
+            }
+        });
     }
 
     #[test]
@@ -146,6 +166,10 @@ mod tests {
         let mut res = vec!['A', 'a', 'h', 'b', 'W'];
         sort_v1(&mut res);
         assert_eq!(res, vec!['A', 'W', 'a', 'b', 'h']);
+
+        let mut res = vec!['A', 'a', 'h', 'b', 'W'];
+        sort_v2(&mut res);
+        assert_eq!(res, vec!['A', 'W', 'a', 'b', 'h']);
     }
 
     #[test]
@@ -153,6 +177,10 @@ mod tests {
         let mut res = Vec::<u8>::new();
         sort_v1(&mut res);
         assert_eq!(res, vec![]);
+
+        let mut res = Vec::<u8>::new();
+        sort_v2(&mut res);
+        assert_eq!(res, vec![]);
     }
 
     #[test]
@@ -160,5 +188,34 @@ mod tests {
         let mut res = vec!['a', 'b', 'c', 'd'];
         sort_v1(&mut res);
         assert_eq!(res, vec!['a', 'b', 'c', 'd']);
+
+        let mut res = vec!['a', 'b', 'c', 'd'];
+        sort_v2(&mut res);
+        assert_eq!(res, vec!['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn by_and_key() {
+        let mut res: Vec<i32> = (0..100).collect();
+        sort_v1_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, (0..100).rev().collect::<Vec<i32>>());
+
+        // 归并排序是稳定的, key 相同的元素保持原来的顺序
+        let arr: Vec<(i32, usize)> = (0..100).map(|i| ((i * 7) % 5, i as usize)).collect();
+        let check = |res: &[(i32, usize)]| {
+            for pair in res.windows(2) {
+                assert!(pair[0].0 < pair[1].0 || pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1);
+            }
+        };
+        let mut res = arr.clone();
+        sort_v1_by_key(&mut res, |p| p.0);
+        check(&res);
+        let mut res = arr.clone();
+        sort_v2_by_key(&mut res, |p| p.0);
+        check(&res);
+
+        let mut res: Vec<i32> = (0..100).collect();
+        sort_v2_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, (0..100).rev().collect::<Vec<i32>>());
     }
 }
@@ -1,6 +1,15 @@
+pub mod bench;
 pub mod bubble;
+pub mod bucket;
+pub mod counting;
 pub mod heap;
 pub mod insertion;
+pub mod intro;
 pub mod merge;
+pub mod parallel;
 pub mod quick;
+pub mod radix;
 pub mod selection;
+pub mod sorter;
+pub mod string;
+pub mod tim;
@@ -0,0 +1,261 @@
+use super::{merge, quick};
+use crate::util::random;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cmp::Ordering;
+use std::sync::OnceLock;
+use std::thread;
+
+// 使用 std::thread::scope 的并行排序, fork/join 模型
+// 每次把数组分成两部分, 一部分交给新的线程, 另一部分在当前线程中排序
+// 线程数按照两部分平均分配, 只剩一个线程或者规模小于 threshold 时使用顺序的排序
+// 比较函数需要在多个线程中共享, 因此要求 Fn + Sync 而不是 FnMut
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Parallel {
+    threads: usize,
+    threshold: usize,
+}
+
+impl Default for Parallel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parallel {
+    // 默认使用所有可用的 CPU, 规模小于 8192 时不再创建线程
+    // 查询 CPU 数量的开销较大, 只查询一次
+    pub fn new() -> Self {
+        static THREADS: OnceLock<usize> = OnceLock::new();
+        Self {
+            threads: *THREADS
+                .get_or_init(|| thread::available_parallelism().map_or(1, |n| n.get())),
+            threshold: 8192,
+        }
+    }
+
+    pub fn threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0);
+        self.threads = threads;
+        self
+    }
+
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn thread_count(&self) -> usize {
+        self.threads
+    }
+
+    // 两部分并行排序之后在当前线程中 merge, 稳定
+    pub fn merge_sort_by<T, F>(&self, arr: &mut [T], compare: F)
+    where
+        T: Clone + Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let mut aux = Vec::with_capacity(arr.len());
+        self.merge_sort(arr, &mut aux, self.threads, &compare);
+    }
+
+    fn merge_sort<T, F>(&self, arr: &mut [T], aux: &mut Vec<T>, threads: usize, compare: &F)
+    where
+        T: Clone + Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let len = arr.len();
+        if threads == 1 || len < self.threshold.max(2) {
+            merge::sort_v1_by(arr, compare);
+            return;
+        }
+
+        let mid = len / 2;
+        let (left, right) = arr.split_at_mut(mid);
+        thread::scope(|s| {
+            s.spawn(|| self.merge_sort(right, &mut Vec::new(), threads - threads / 2, compare));
+            self.merge_sort(left, aux, threads / 2, compare);
+        });
+
+        // 两部分已经有序时不需要 merge
+        if compare(&arr[mid], &arr[mid - 1]) == Ordering::Less {
+            merge::merge(arr, aux, 0, mid - 1, len - 1, &mut &*compare);
+        }
+    }
+
+    // 3-ways partition 之后两部分并行排序
+    pub fn quick_sort_by<T, F>(&self, arr: &mut [T], compare: F)
+    where
+        T: Clone + Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        self.quick_sort_by_with_rng(arr, compare, &mut random::rng());
+    }
+
+    // 由 rng 选择 pivot, 新的线程使用从 rng 派生的随机数生成器
+    pub fn quick_sort_by_with_rng<T, F>(&self, arr: &mut [T], compare: F, rng: &mut StdRng)
+    where
+        T: Clone + Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        self.quick_sort(arr, self.threads, &compare, rng);
+    }
+
+    fn quick_sort<T, F>(&self, arr: &mut [T], threads: usize, compare: &F, rng: &mut StdRng)
+    where
+        T: Clone + Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let len = arr.len();
+        if threads == 1 || len < self.threshold.max(2) {
+            quick::sort_v4_by_with_rng(arr, compare, rng);
+            return;
+        }
+
+        let (lt, gt) = quick::partition_v4(arr, 0, len - 1, &mut &*compare, rng);
+        let (left, right) = arr.split_at_mut(gt);
+        let left = &mut left[..lt];
+        let mut child = StdRng::from_rng(&mut *rng).unwrap();
+        thread::scope(|s| {
+            s.spawn(move || self.quick_sort(right, threads - threads / 2, compare, &mut child));
+            self.quick_sort(left, threads / 2, compare, rng);
+        });
+    }
+}
+
+pub fn merge_sort<T: Ord + Clone + Send>(arr: &mut [T]) {
+    merge_sort_by(arr, T::cmp);
+}
+
+pub fn merge_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    Parallel::new().merge_sort_by(arr, compare);
+}
+
+pub fn merge_sort_by_key<T, K, F>(arr: &mut [T], f: F)
+where
+    T: Clone + Send,
+    K: Ord,
+    F: Fn(&T) -> K + Sync,
+{
+    merge_sort_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
+pub fn quick_sort<T: Ord + Clone + Send>(arr: &mut [T]) {
+    quick_sort_by(arr, T::cmp);
+}
+
+pub fn quick_sort_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    Parallel::new().quick_sort_by(arr, compare);
+}
+
+pub fn quick_sort_by_key<T, K, F>(arr: &mut [T], f: F)
+where
+    T: Clone + Send,
+    K: Ord,
+    F: Fn(&T) -> K + Sync,
+{
+    quick_sort_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{self, generate};
+    use rand::Rng;
+
+    #[test]
+    fn integer() {
+        let mut res = vec![4, 1, 8, 5, 7];
+        merge_sort(&mut res);
+        assert_eq!(res, vec![1, 4, 5, 7, 8]);
+
+        let mut res = vec![4, 1, 8, 5, 7];
+        quick_sort(&mut res);
+        assert_eq!(res, vec![1, 4, 5, 7, 8]);
+
+        let mut res: Vec<i32> = vec![];
+        merge_sort(&mut res);
+        quick_sort(&mut res);
+        assert!(res.is_empty());
+    }
+
+    #[test]
+    fn threads() {
+        random::reproducible(|| {
+            let seed: u64 = random::rng().gen();
+            for threads in [1, 2, 3, 8] {
+                let parallel = Parallel::new().threads(threads).threshold(64);
+                for n in [1, 2, 63, 64, 65, 1000, 20000] {
+                    let inputs: Vec<Vec<i32>> = vec![
+                        generate::uniform(n, 0, n as i64, seed),
+                        generate::few_unique(n, 3, seed),
+                        generate::sorted(n),
+                        generate::reversed(n),
+                    ];
+                    for arr in inputs {
+                        let mut res = arr.clone();
+                        parallel.merge_sort_by(&mut res, i32::cmp);
+                        assert!(util::is_sorted(&res));
+                        let mut res = arr.clone();
+                        parallel.quick_sort_by(&mut res, i32::cmp);
+                        assert!(util::is_sorted(&res));
+                    }
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn by_and_key() {
+        let mut res: Vec<i32> = (0..20000).collect();
+        quick_sort_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, (0..20000).rev().collect::<Vec<i32>>());
+
+        let mut res: Vec<i32> = (-10000..10000).collect();
+        quick_sort_by_key(&mut res, |x| x.abs());
+        assert!(res.windows(2).all(|p| p[0].abs() <= p[1].abs()));
+
+        // 并行的 merge sort 同样是稳定的
+        let mut res: Vec<(i32, usize)> = (0..20000).map(|i| ((i * 7) % 5, i as usize)).collect();
+        let parallel = Parallel::new().threads(4).threshold(100);
+        parallel.merge_sort_by(&mut res, |a, b| a.0.cmp(&b.0));
+        for pair in res.windows(2) {
+            assert!(pair[0].0 < pair[1].0 || pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1);
+        }
+        merge_sort_by_key(&mut res, |p| std::cmp::Reverse(p.0));
+        assert!(res.windows(2).all(|p| p[0].0 >= p[1].0));
+    }
+
+    #[test]
+    fn seeded() {
+        // 相同的 seed 得到相同的比较次数
+        let arr: Vec<i32> = generate::uniform(50000, 0, 1000, 1);
+        let count = || {
+            let calls = std::sync::atomic::AtomicUsize::new(0);
+            let mut res = arr.clone();
+            Parallel::new()
+                .threads(4)
+                .threshold(1000)
+                .quick_sort_by_with_rng(
+                    &mut res,
+                    |a, b| {
+                        calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        a.cmp(b)
+                    },
+                    &mut StdRng::seed_from_u64(7),
+                );
+            assert!(util::is_sorted(&res));
+            calls.into_inner()
+        };
+        assert_eq!(count(), count());
+    }
+}
@@ -1,8 +1,13 @@
-use crate::util;
-use rand::{self, Rng};
+use crate::util::random;
+use rand::Rng;
+use std::cmp::Ordering;
 
 // 对arr[low:high]进行partition操作，将第1个元素放在排序后的位置
-fn partition_v1<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize {
+fn partition_v1<T, F>(arr: &mut [T], low: usize, high: usize, compare: &mut F) -> usize
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     // pick first element as pivot
     let e = arr[low].clone();
 
@@ -11,7 +16,7 @@ fn partition_v1<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize
     // arr[j+1:i) > v
     let mut i = low + 1;
     while i <= high {
-        if arr[i].clone() < e {
+        if compare(&arr[i], &e) == Ordering::Less {
             j += 1;
             arr.swap(j, i);
         }
@@ -22,47 +27,83 @@ fn partition_v1<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize
     j
 }
 
-fn quick_sort_v1<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) {
+fn quick_sort_v1<T, F>(arr: &mut [T], low: usize, high: usize, compare: &mut F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
     if low >= high {
         return;
     }
 
-    let p = partition_v1(arr, low, high);
+    let p = partition_v1(arr, low, high, compare);
     if p > 0 {
-        quick_sort_v1(arr, low, p - 1);
+        quick_sort_v1(arr, low, p - 1, compare);
     }
-    quick_sort_v1(arr, p + 1, high);
+    quick_sort_v1(arr, p + 1, high, compare);
 }
 
 pub fn sort_v1<T: Ord + Clone>(arr: &mut [T]) {
-    if arr.len() > 0 {
+    sort_v1_by(arr, T::cmp);
+}
+
+pub fn sort_v1_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if !arr.is_empty() {
         let high = arr.len() - 1;
-        quick_sort_v1(arr, 0, high)
+        quick_sort_v1(arr, 0, high, &mut compare)
     }
 }
 
+pub fn sort_v1_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v1_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
 // 优化一：小规模数组使用insertion sort进行优化
-fn quick_sort_v2<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) {
+fn quick_sort_v2<T, F, R>(arr: &mut [T], low: usize, high: usize, compare: &mut F, rng: &mut R)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
     if low >= high {
         return;
     }
 
     if high - low <= 15 {
-        super::insertion::sort_v3(arr, low, high);
+        super::insertion::sort_v3_by(arr, low, high, &mut *compare);
         return;
     }
 
-    let p = partition_v2(arr, low, high);
+    let p = partition_v2(arr, low, high, compare, rng);
     if p > 0 {
-        quick_sort_v2(arr, low, p - 1);
+        quick_sort_v2(arr, low, p - 1, compare, rng);
     }
-    quick_sort_v2(arr, p + 1, high);
+    quick_sort_v2(arr, p + 1, high, compare, rng);
 }
 
 // 优化二：选择随机数作为pivot
-fn partition_v2<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize {
+fn partition_v2<T, F, R>(
+    arr: &mut [T],
+    low: usize,
+    high: usize,
+    compare: &mut F,
+    rng: &mut R,
+) -> usize
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
     // pick a random element as pivot
-    let mut rng = rand::thread_rng();
     arr.swap(low, rng.gen_range(low, high));
     let e = arr[low].clone();
 
@@ -71,7 +112,7 @@ fn partition_v2<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize
     // arr[j+1:i) > v
     let mut i = low + 1;
     while i <= high {
-        if arr[i].clone() < e {
+        if compare(&arr[i], &e) == Ordering::Less {
             j += 1;
             arr.swap(j, i);
         }
@@ -83,16 +124,53 @@ fn partition_v2<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize
 }
 
 pub fn sort_v2<T: Ord + Clone>(arr: &mut [T]) {
-    if arr.len() > 0 {
+    sort_v2_by(arr, T::cmp);
+}
+
+pub fn sort_v2_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_v2_by_with_rng(arr, compare, &mut random::rng());
+}
+
+// 由 rng 选择 pivot, 相同的 rng 状态得到相同的比较序列
+pub fn sort_v2_by_with_rng<T, F, R>(arr: &mut [T], mut compare: F, rng: &mut R)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    if !arr.is_empty() {
         let high = arr.len() - 1;
-        quick_sort_v2(arr, 0, high)
+        quick_sort_v2(arr, 0, high, &mut compare, rng)
     }
 }
 
+pub fn sort_v2_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v2_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
 // 优化三：二路排序，均分重复元素
-fn partition_v3<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize {
+fn partition_v3<T, F, R>(
+    arr: &mut [T],
+    low: usize,
+    high: usize,
+    compare: &mut F,
+    rng: &mut R,
+) -> usize
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
     // pick a random element as pivot
-    let mut rng = rand::thread_rng();
     arr.swap(low, rng.gen_range(low, high));
     let e = arr[low].clone();
 
@@ -103,12 +181,12 @@ fn partition_v3<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize
 
     loop {
         // 不能是 arr[i] <= e，否则会导致重复元素分布不均
-        while i <= high && arr[i] < e {
+        while i <= high && compare(&arr[i], &e) == Ordering::Less {
             i += 1;
         }
 
         // 不能是 arr[j] >= e，否则会导致重复元素分布不均
-        while j >= low + 1 && arr[j] > e {
+        while j > low && compare(&arr[j], &e) == Ordering::Greater {
             j -= 1;
         }
 
@@ -125,44 +203,76 @@ fn partition_v3<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) -> usize
     j
 }
 
-fn quick_sort_v3<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) {
+fn quick_sort_v3<T, F, R>(arr: &mut [T], low: usize, high: usize, compare: &mut F, rng: &mut R)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
     if low >= high {
         return;
     }
 
     if high - low <= 15 {
-        super::insertion::sort_v3(arr, low, high);
+        super::insertion::sort_v3_by(arr, low, high, &mut *compare);
         return;
     }
 
-    let p = partition_v3(arr, low, high);
+    let p = partition_v3(arr, low, high, compare, rng);
     if p > 0 {
-        quick_sort_v3(arr, low, p - 1);
+        quick_sort_v3(arr, low, p - 1, compare, rng);
     }
-    quick_sort_v3(arr, p + 1, high);
+    quick_sort_v3(arr, p + 1, high, compare, rng);
 }
 
 pub fn sort_v3<T: Ord + Clone>(arr: &mut [T]) {
-    if arr.len() > 0 {
-        let high = arr.len() - 1;
-        quick_sort_v3(arr, 0, high)
-    }
+    sort_v3_by(arr, T::cmp);
 }
 
-// 优化四：3-ways quick sort
-// 优势在于处理大量重复元素的场景
-fn quick_sort_v4<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) {
-    if low >= high {
-        return;
-    }
+pub fn sort_v3_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_v3_by_with_rng(arr, compare, &mut random::rng());
+}
 
-    if high - low <= 15 {
-        super::insertion::sort_v3(arr, low, high);
-        return;
+// 由 rng 选择 pivot, 相同的 rng 状态得到相同的比较序列
+pub fn sort_v3_by_with_rng<T, F, R>(arr: &mut [T], mut compare: F, rng: &mut R)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    if !arr.is_empty() {
+        let high = arr.len() - 1;
+        quick_sort_v3(arr, 0, high, &mut compare, rng)
     }
+}
+
+pub fn sort_v3_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v3_by(arr, |a, b| f(a).cmp(&f(b)));
+}
 
-    // 3-ways quick sort
-    let mut rng = rand::thread_rng();
+// 3-ways partition, 随机选择 pivot e
+// 返回 (lt, gt), 此时 arr[low:lt-1] < e, arr[lt:gt-1] == e, arr[gt:high] > e
+pub(super) fn partition_v4<T, F, R>(
+    arr: &mut [T],
+    low: usize,
+    high: usize,
+    compare: &mut F,
+    rng: &mut R,
+) -> (usize, usize)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
     arr.swap(low, rng.gen_range(low, high));
     let e = arr[low].clone();
 
@@ -178,230 +288,97 @@ fn quick_sort_v4<T: Ord + Clone>(arr: &mut [T], low: usize, high: usize) {
             break;
         }
 
-        // arr[low+1:lt] < e
-        if arr[i] < e {
-            arr.swap(i, lt + 1);
-            lt += 1;
-            i += 1;
-        // arr[gt:high] > e
-        } else if arr[i] > e {
-            arr.swap(i, gt - 1);
-            gt -= 1;
-        // arr[lt+1:i-1] == e
-        } else {
-            i += 1;
+        match compare(&arr[i], &e) {
+            // arr[low+1:lt] < e
+            Ordering::Less => {
+                arr.swap(i, lt + 1);
+                lt += 1;
+                i += 1;
+            }
+            // arr[gt:high] > e
+            Ordering::Greater => {
+                arr.swap(i, gt - 1);
+                gt -= 1;
+            }
+            // arr[lt+1:i-1] == e
+            Ordering::Equal => {
+                i += 1;
+            }
         }
     }
 
     // make sure e in the orerdred position
     arr.swap(low, lt);
     // 此时，arr[low+1:lt-1] < e
+    (lt, gt)
+}
+
+// 优化四：3-ways quick sort
+// 优势在于处理大量重复元素的场景
+fn quick_sort_v4<T, F, R>(arr: &mut [T], low: usize, high: usize, compare: &mut F, rng: &mut R)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    if low >= high {
+        return;
+    }
+
+    if high - low <= 15 {
+        super::insertion::sort_v3_by(arr, low, high, &mut *compare);
+        return;
+    }
 
+    let (lt, gt) = partition_v4(arr, low, high, compare, rng);
     if lt > 0 {
-        quick_sort_v4(arr, low, lt - 1);
+        quick_sort_v4(arr, low, lt - 1, compare, rng);
     }
-    quick_sort_v4(arr, gt, high);
+    quick_sort_v4(arr, gt, high, compare, rng);
 }
 
 pub fn sort_v4<T: Ord + Clone>(arr: &mut [T]) {
-    if arr.len() > 0 {
+    sort_v4_by(arr, T::cmp);
+}
+
+pub fn sort_v4_by<T, F>(arr: &mut [T], compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    sort_v4_by_with_rng(arr, compare, &mut random::rng());
+}
+
+// 由 rng 选择 pivot, 相同的 rng 状态得到相同的比较序列
+pub fn sort_v4_by_with_rng<T, F, R>(arr: &mut [T], mut compare: F, rng: &mut R)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+    R: Rng,
+{
+    if !arr.is_empty() {
         let high = arr.len() - 1;
-        quick_sort_v4(arr, 0, high)
+        quick_sort_v4(arr, 0, high, &mut compare, rng)
     }
 }
 
-pub fn run() {
-    println!("Test for random array in 1-n .");
-
-    let n = 100;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    let mut arr9 = arr1.clone();
-    util::test_sort("selection sort", super::selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", super::insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", super::insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr8);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr9);
-
-    let n = 1000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    let mut arr9 = arr1.clone();
-    util::test_sort("selection sort", super::selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", super::insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", super::insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr8);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr9);
-
-    let n = 10000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    let mut arr9 = arr1.clone();
-    util::test_sort("selection sort", super::selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", super::insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", super::insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr8);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr9);
-
-    let n = 100000;
-    let mut arr1 = util::generate_random_array(n, 1, n);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr1);
-    util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr2);
-    util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr3);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr4);
-    util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr5);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr6);
-
-    let swap_times = 10;
-    println!(
-        "Test for nearly ordered array, swap_times = {} .",
-        swap_times
-    );
-
-    let n = 100;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    let mut arr9 = arr1.clone();
-    util::test_sort("selection sort", super::selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", super::insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", super::insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr8);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr9);
-
-    let n = 1000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    let mut arr9 = arr1.clone();
-    util::test_sort("selection sort", super::selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", super::insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", super::insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr8);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr9);
-
-    let n = 10000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    let mut arr2 = arr1.clone();
-    let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    let mut arr9 = arr1.clone();
-    util::test_sort("selection sort", super::selection::sort, &mut arr1);
-    util::test_sort("insertion sort_v1", super::insertion::sort_v1, &mut arr2);
-    util::test_sort("insertion sort_v2", super::insertion::sort_v2, &mut arr3);
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr4);
-    util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr5);
-    util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr6);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr8);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr9);
-
-    let swap_times = 100;
-    println!(
-        "Test for nearly ordered array, swap_times = {} .",
-        swap_times
-    );
-    let n = 1000000;
-    let mut arr1 = util::generate_nearly_ordered_array(n, swap_times);
-    // let mut arr2 = arr1.clone();
-    // let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    // let mut arr5 = arr1.clone();
-    let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr1);
-    // util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr2);
-    // util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr3);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr4);
-    // util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr5);
-    util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr6);
-    util::test_sort("quick sort_v3", super::quick::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v4", super::quick::sort_v4, &mut arr8);
-
-    println!("Test for many duplication element array, random range [0,10].");
-    let n = 1000000;
-    let mut arr1 = util::generate_random_array(n, 0, 10);
-    // let mut arr2 = arr1.clone();
-    // let mut arr3 = arr1.clone();
-    let mut arr4 = arr1.clone();
-    // let mut arr5 = arr1.clone();
-    // let mut arr6 = arr1.clone();
-    let mut arr7 = arr1.clone();
-    let mut arr8 = arr1.clone();
-    util::test_sort("merge sort_v1", super::merge::sort_v1, &mut arr1);
-    // util::test_sort("heap sort_v1", super::heap::sort_v1, &mut arr2);
-    // util::test_sort("heap sort_v2", super::heap::sort_v2, &mut arr3);
-    util::test_sort("heap sort_v3", super::heap::sort_v3, &mut arr4);
-    // util::test_sort("quick sort_v1", super::quick::sort_v1, &mut arr5);
-
-    // sort_v2 跑不出来结果，报错如下，因此注释掉
-    // thread 'main' has overflowed its stack
-    // util::test_sort("quick sort_v2", super::quick::sort_v2, &mut arr6);
-    util::test_sort("quick sort_v3", super::quick::sort_v3, &mut arr7);
-    util::test_sort("quick sort_v4", super::quick::sort_v4, &mut arr8);
+pub fn sort_v4_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_v4_by(arr, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::{self, generate};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    type SortWithRng = fn(&mut [usize], &mut dyn FnMut(&usize, &usize) -> Ordering, &mut StdRng);
 
     #[test]
     fn integer() {
@@ -478,4 +455,80 @@ mod tests {
         sort_v4(&mut res);
         assert_eq!(res, vec!['a', 'b', 'c', 'd']);
     }
+
+    #[test]
+    fn by_and_key() {
+        let expected: Vec<i32> = (0..100).rev().collect();
+        let mut res: Vec<i32> = (0..100).collect();
+        sort_v1_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, expected);
+        let mut res: Vec<i32> = (0..100).collect();
+        sort_v2_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, expected);
+        let mut res: Vec<i32> = (0..100).collect();
+        sort_v3_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, expected);
+        let mut res: Vec<i32> = (0..100).collect();
+        sort_v4_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, expected);
+
+        // 按照绝对值排序
+        let arr: Vec<i32> = (-50..50).collect();
+        let check = |res: &[i32]| res.windows(2).all(|p| p[0].abs() <= p[1].abs());
+        let mut res = arr.clone();
+        sort_v1_by_key(&mut res, |x| x.abs());
+        assert!(check(&res));
+        let mut res = arr.clone();
+        sort_v2_by_key(&mut res, |x| x.abs());
+        assert!(check(&res));
+        let mut res = arr.clone();
+        sort_v3_by_key(&mut res, |x| x.abs());
+        assert!(check(&res));
+        let mut res = arr.clone();
+        sort_v4_by_key(&mut res, |x| x.abs());
+        assert!(check(&res));
+    }
+
+    #[test]
+    fn seeded_worst_case() {
+        random::reproducible(|| {
+            let n = 1000;
+            let seed: u64 = random::rng().gen();
+            let count = |arr: &[usize], sort: SortWithRng| {
+                let mut calls = 0;
+                let mut arr = arr.to_vec();
+                sort(
+                    &mut arr,
+                    &mut |a, b| {
+                        calls += 1;
+                        a.cmp(b)
+                    },
+                    &mut StdRng::seed_from_u64(seed),
+                );
+                assert!(util::is_sorted(&arr));
+                calls
+            };
+            let sorts: [SortWithRng; 3] = [
+                |arr, compare, rng| sort_v2_by_with_rng(arr, compare, rng),
+                |arr, compare, rng| sort_v3_by_with_rng(arr, compare, rng),
+                |arr, compare, rng| sort_v4_by_with_rng(arr, compare, rng),
+            ];
+
+            // 相同的 seed 选择相同的 pivot, 针对这个 seed 构造的输入会退化为 O(n^2)
+            for sort in sorts {
+                let killer: Vec<usize> = generate::quicksort_killer(n, |arr, compare| {
+                    sort(arr, compare, &mut StdRng::seed_from_u64(seed))
+                });
+                let random: Vec<usize> = generate::uniform(n, 0, n as i64, seed);
+                assert!(count(&killer, sort) >= n * n / 8);
+                assert!(count(&random, sort) < n * n / 20);
+            }
+
+            // 只有 v2 在所有元素相同时退化
+            let equal: Vec<usize> = generate::all_equal(n, 0);
+            assert!(count(&equal, sorts[0]) >= n * n / 4);
+            assert!(count(&equal, sorts[1]) < n * n / 20);
+            assert!(count(&equal, sorts[2]) < n * n / 20);
+        });
+    }
 }
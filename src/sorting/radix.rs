@@ -0,0 +1,173 @@
+use super::insertion;
+
+// 基数排序, 按照字节把整数分配到 256 个桶中, 不需要比较
+// LSD 从最低字节开始, 每一轮都是稳定的 counting sort, 复杂度为 O(BYTES * (n + 256))
+// MSD 从最高字节开始, 对每个桶递归排序, 小规模的桶使用 insertion sort
+
+// 可以按照字节排序的整数
+// bits 把整数映射为无符号整数, 并且保持大小顺序, 有符号整数需要翻转符号位
+pub trait RadixKey: Copy + Ord {
+    const BYTES: usize;
+    fn bits(self) -> u128;
+
+    // 第 i 个字节, 0 为最低字节
+    fn byte(self, i: usize) -> u8 {
+        (self.bits() >> (8 * i)) as u8
+    }
+}
+
+macro_rules! radix_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn bits(self) -> u128 {
+                    self as u128
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! radix_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                // 翻转符号位后负数小于正数, 例如 i8: -128 -> 0, -1 -> 127, 0 -> 128
+                fn bits(self) -> u128 {
+                    ((self as $u) ^ (<$u>::MAX / 2 + 1)) as u128
+                }
+            }
+        )*
+    };
+}
+
+radix_unsigned!(u8, u16, u32, u64, u128, usize);
+radix_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+// 按照第 i 个字节把 arr 稳定地分配到 aux 中, 返回每个桶的起始位置
+fn distribute<T: RadixKey>(arr: &[T], aux: &mut [T], i: usize) -> [usize; 257] {
+    let mut count = [0; 257];
+    for x in arr.iter() {
+        count[x.byte(i) as usize + 1] += 1;
+    }
+    for r in 0..256 {
+        count[r + 1] += count[r];
+    }
+
+    let mut next = count;
+    for &x in arr.iter() {
+        let b = x.byte(i) as usize;
+        aux[next[b]] = x;
+        next[b] += 1;
+    }
+    count
+}
+
+pub fn lsd_sort<T: RadixKey>(arr: &mut [T]) {
+    if arr.len() < 2 {
+        return;
+    }
+
+    let mut aux = arr.to_vec();
+    for i in 0..T::BYTES {
+        // 所有元素的这个字节都相同时跳过
+        let b = arr[0].byte(i);
+        if arr.iter().all(|x| x.byte(i) == b) {
+            continue;
+        }
+        distribute(arr, &mut aux, i);
+        arr.copy_from_slice(&aux);
+    }
+}
+
+// 规模不超过 CUTOFF 时使用 insertion sort
+const CUTOFF: usize = 16;
+
+fn msd<T: RadixKey>(arr: &mut [T], aux: &mut [T], i: usize) {
+    if arr.len() <= CUTOFF {
+        if arr.len() > 1 {
+            insertion::sort_v3_by(arr, 0, arr.len() - 1, T::cmp);
+        }
+        return;
+    }
+
+    let count = distribute(arr, aux, i);
+    arr.copy_from_slice(aux);
+    if i == 0 {
+        return;
+    }
+    for r in 0..256 {
+        let (start, end) = (count[r], count[r + 1]);
+        msd(&mut arr[start..end], &mut aux[start..end], i - 1);
+    }
+}
+
+pub fn msd_sort<T: RadixKey>(arr: &mut [T]) {
+    let mut aux = arr.to_vec();
+    msd(arr, &mut aux, T::BYTES - 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{self, generate, random};
+    use rand::Rng;
+
+    fn check<T: RadixKey + std::fmt::Debug>(arr: &[T]) {
+        let mut expected = arr.to_vec();
+        expected.sort();
+        let mut res = arr.to_vec();
+        lsd_sort(&mut res);
+        assert!(util::is_sorted(&res));
+        assert_eq!(res, expected);
+        let mut res = arr.to_vec();
+        msd_sort(&mut res);
+        assert!(util::is_sorted(&res));
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn bits() {
+        assert!((-1i8).bits() < 0i8.bits());
+        assert_eq!(i8::MIN.bits(), 0);
+        assert_eq!(i64::MAX.bits(), u64::MAX as u128);
+        assert_eq!(0x1234u16.byte(1), 0x12);
+        assert_eq!((-1i32).byte(3), 0x7f);
+    }
+
+    #[test]
+    fn widths() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for n in [0, 1, 2, 16, 17, 100, 1000, 5000] {
+                check::<u8>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<i8>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<u16>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<i16>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<u32>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<i32>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<u64>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<i64>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<u128>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<i128>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<usize>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+                check::<isize>(&(0..n).map(|_| rng.gen()).collect::<Vec<_>>());
+            }
+        });
+    }
+
+    #[test]
+    fn distributions() {
+        let n = 3000;
+        check::<i32>(&generate::reversed(n));
+        check::<i32>(&generate::all_equal(n, -7));
+        check::<i64>(&generate::few_unique(n, 5, 1));
+        check::<i16>(&generate::gaussian(n, 0.0, 1000.0, 2));
+        check::<u32>(&generate::zipf(n, 100, 1.2, 3));
+        check(&[i32::MIN, i32::MAX, 0, -1, 1, i32::MIN + 1, i32::MAX - 1]);
+    }
+}
@@ -1,6 +1,14 @@
-use crate::util;
+use std::cmp::Ordering;
 
 pub fn sort<T: Ord>(arr: &mut [T]) {
+    sort_by(arr, T::cmp);
+}
+
+// 使用自定义的比较函数排序, 例如 |a, b| b.cmp(a) 表示降序
+pub fn sort_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
     let len = arr.len();
 
     // 遍历数组，确定第 i 个位置应该放置的元素
@@ -9,7 +17,7 @@ pub fn sort<T: Ord>(arr: &mut [T]) {
         let mut min_index = i;
         // 寻找剩余范围最小元素的 index
         for j in i..len {
-            if arr[j] < arr[min_index] {
+            if compare(&arr[j], &arr[min_index]) == Ordering::Less {
                 min_index = j
             }
         }
@@ -18,27 +26,13 @@ pub fn sort<T: Ord>(arr: &mut [T]) {
     }
 }
 
-// 简单测试sort的时间复杂度
-// 对不同数量的元素进行排序，观察元素数量和排序时间的关系
-pub fn run() {
-    println!("Test for random array in 1-n .");
-
-    let n = 100;
-    let mut arr = util::generate_random_array(n, 1, n);
-    util::test_sort("selection sort", sort, &mut arr);
-
-    let n = 1000;
-    let mut arr = util::generate_random_array(n, 1, n);
-    util::test_sort("selection sort", sort, &mut arr);
-
-    let n = 10000;
-    let mut arr = util::generate_random_array(n, 1, n);
-    util::test_sort("selection sort", sort, &mut arr);
-
-    // 如下需要执行247秒左右
-    // let n = 100000;
-    // let mut arr = util::generate_random_array(n, 1, n);
-    // util::test_sort("selection sort", sort, &mut arr);
+// 按照 f 返回的 key 排序
+pub fn sort_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(arr, |a, b| f(a).cmp(&f(b)));
 }
 
 #[cfg(test)]
@@ -72,4 +66,15 @@ mod tests {
         sort(&mut res);
         assert_eq!(res, vec!['a', 'b', 'c', 'd']);
     }
+
+    #[test]
+    fn by_and_key() {
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort_by(&mut res, |a, b| b.cmp(a));
+        assert_eq!(res, vec![8, 7, 5, 4, 1]);
+
+        let mut res = vec![(1, 'c'), (2, 'a'), (3, 'b')];
+        sort_by_key(&mut res, |p| p.1);
+        assert_eq!(res, vec![(2, 'a'), (3, 'b'), (1, 'c')]);
+    }
 }
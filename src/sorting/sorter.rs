@@ -0,0 +1,722 @@
+use crate::sorting::bench::{Bench, Distribution, Format, Measurement};
+use crate::sorting::parallel::Parallel;
+use crate::sorting::{
+    bubble, bucket, counting, heap, insertion, intro, merge, parallel, quick, radix, selection,
+    string, tim,
+};
+use crate::util::{self, random};
+use rand::Rng;
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+use std::path::Path;
+
+// 时间复杂度
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Complexity {
+    // O(n log n)
+    Linearithmic,
+    // O(n^2)
+    Quadratic,
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Complexity::Linearithmic => write!(f, "O(n log n)"),
+            Complexity::Quadratic => write!(f, "O(n^2)"),
+        }
+    }
+}
+
+// 比较函数需要在并行排序的多个线程中共享, 因此是 Fn + Sync
+pub type Compare<'a, T> = &'a (dyn Fn(&T, &T) -> Ordering + Sync);
+
+// 统一的排序算法接口, 以及算法的基本信息
+pub trait Sorter<T> {
+    fn name(&self) -> &str;
+    // 相等的元素是否保持原来的顺序
+    fn stable(&self) -> bool;
+    // 除了递归调用栈之外是否只使用 O(1) 的额外空间
+    fn in_place(&self) -> bool;
+    fn worst_case(&self) -> Complexity;
+    fn average_case(&self) -> Complexity;
+    fn sort(&self, arr: &mut [T]);
+    fn sort_by(&self, arr: &mut [T], compare: Compare<T>);
+}
+
+pub type SortFn<T> = fn(&mut [T]);
+type SortByFn<T> = fn(&mut [T], Compare<T>);
+
+// 由各个模块中的 sort 和 sort_by 函数组成的 Sorter
+pub struct FnSorter<T> {
+    name: &'static str,
+    stable: bool,
+    in_place: bool,
+    worst_case: Complexity,
+    average_case: Complexity,
+    sort: SortFn<T>,
+    sort_by: SortByFn<T>,
+}
+
+impl<T> Sorter<T> for FnSorter<T> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn stable(&self) -> bool {
+        self.stable
+    }
+
+    fn in_place(&self) -> bool {
+        self.in_place
+    }
+
+    fn worst_case(&self) -> Complexity {
+        self.worst_case
+    }
+
+    fn average_case(&self) -> Complexity {
+        self.average_case
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        (self.sort)(arr)
+    }
+
+    fn sort_by(&self, arr: &mut [T], compare: Compare<T>) {
+        (self.sort_by)(arr, compare)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ParallelKind {
+    Merge,
+    Quick,
+}
+
+// 使用 Parallel 的排序, 名称中包含线程数, 例如 "parallel merge sort x4"
+// 元素需要在线程之间传递, 只能用于 T: Send, 因此不放在 registry 中
+pub struct ParallelSorter {
+    name: String,
+    kind: ParallelKind,
+    parallel: Parallel,
+}
+
+impl<T: Ord + Clone + Send> Sorter<T> for ParallelSorter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn stable(&self) -> bool {
+        self.kind == ParallelKind::Merge
+    }
+
+    fn in_place(&self) -> bool {
+        self.kind == ParallelKind::Quick
+    }
+
+    fn worst_case(&self) -> Complexity {
+        match self.kind {
+            ParallelKind::Merge => Complexity::Linearithmic,
+            ParallelKind::Quick => Complexity::Quadratic,
+        }
+    }
+
+    fn average_case(&self) -> Complexity {
+        Complexity::Linearithmic
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        self.sort_by(arr, &T::cmp)
+    }
+
+    fn sort_by(&self, arr: &mut [T], compare: Compare<T>) {
+        match self.kind {
+            ParallelKind::Merge => self.parallel.merge_sort_by(arr, compare),
+            ParallelKind::Quick => self.parallel.quick_sort_by(arr, compare),
+        }
+    }
+}
+
+// 按照 (名称, 稳定, 原地, 最坏复杂度, 平均复杂度, sort, sort_by) 的顺序构造 FnSorter
+macro_rules! sorter {
+    ($name:expr, $stable:expr, $in_place:expr, $worst:ident, $average:ident, $sort:expr, $sort_by:expr) => {
+        Box::new(FnSorter {
+            name: $name,
+            stable: $stable,
+            in_place: $in_place,
+            worst_case: Complexity::$worst,
+            average_case: Complexity::$average,
+            sort: $sort,
+            sort_by: $sort_by,
+        })
+    };
+}
+
+// 所有排序算法, 按照模块中出现的顺序排列
+pub fn registry<T: Ord + Clone + 'static>() -> Vec<Box<dyn Sorter<T>>> {
+    vec![
+        sorter!(
+            "selection sort",
+            false,
+            true,
+            Quadratic,
+            Quadratic,
+            selection::sort,
+            |arr, compare| selection::sort_by(arr, compare)
+        ),
+        sorter!(
+            "bubble sort_v1",
+            true,
+            true,
+            Quadratic,
+            Quadratic,
+            bubble::sort_v1,
+            |arr, compare| bubble::sort_v1_by(arr, compare)
+        ),
+        sorter!(
+            "insertion sort_v1",
+            true,
+            true,
+            Quadratic,
+            Quadratic,
+            insertion::sort_v1,
+            |arr, compare| insertion::sort_v1_by(arr, compare)
+        ),
+        sorter!(
+            "insertion sort_v2",
+            true,
+            true,
+            Quadratic,
+            Quadratic,
+            insertion::sort_v2,
+            |arr, compare| insertion::sort_v2_by(arr, compare)
+        ),
+        sorter!(
+            "insertion sort_v3",
+            true,
+            true,
+            Quadratic,
+            Quadratic,
+            |arr| {
+                if !arr.is_empty() {
+                    insertion::sort_v3(arr, 0, arr.len() - 1)
+                }
+            },
+            |arr, compare| {
+                if !arr.is_empty() {
+                    insertion::sort_v3_by(arr, 0, arr.len() - 1, compare)
+                }
+            }
+        ),
+        sorter!(
+            "merge sort_v1",
+            true,
+            false,
+            Linearithmic,
+            Linearithmic,
+            merge::sort_v1,
+            |arr, compare| merge::sort_v1_by(arr, compare)
+        ),
+        sorter!(
+            "merge sort_v2",
+            true,
+            false,
+            Linearithmic,
+            Linearithmic,
+            merge::sort_v2,
+            |arr, compare| merge::sort_v2_by(arr, compare)
+        ),
+        sorter!(
+            "heap sort_v1",
+            false,
+            false,
+            Linearithmic,
+            Linearithmic,
+            heap::sort_v1,
+            |arr, compare| heap::sort_v1_by(arr, compare)
+        ),
+        sorter!(
+            "heap sort_v2",
+            false,
+            false,
+            Linearithmic,
+            Linearithmic,
+            heap::sort_v2,
+            |arr, compare| heap::sort_v2_by(arr, compare)
+        ),
+        sorter!(
+            "heap sort_v3",
+            false,
+            true,
+            Linearithmic,
+            Linearithmic,
+            heap::sort_v3,
+            |arr, compare| heap::sort_v3_by(arr, compare)
+        ),
+        sorter!(
+            "quick sort_v1",
+            false,
+            true,
+            Quadratic,
+            Linearithmic,
+            quick::sort_v1,
+            |arr, compare| quick::sort_v1_by(arr, compare)
+        ),
+        sorter!(
+            "quick sort_v2",
+            false,
+            true,
+            Quadratic,
+            Linearithmic,
+            quick::sort_v2,
+            |arr, compare| quick::sort_v2_by(arr, compare)
+        ),
+        sorter!(
+            "quick sort_v3",
+            false,
+            true,
+            Quadratic,
+            Linearithmic,
+            quick::sort_v3,
+            |arr, compare| quick::sort_v3_by(arr, compare)
+        ),
+        sorter!(
+            "quick sort_v4",
+            false,
+            true,
+            Quadratic,
+            Linearithmic,
+            quick::sort_v4,
+            |arr, compare| quick::sort_v4_by(arr, compare)
+        ),
+        sorter!(
+            "intro sort",
+            false,
+            true,
+            Linearithmic,
+            Linearithmic,
+            intro::sort,
+            |arr, compare| intro::sort_by(arr, compare)
+        ),
+        sorter!(
+            "tim sort",
+            true,
+            false,
+            Linearithmic,
+            Linearithmic,
+            tim::sort,
+            |arr, compare| tim::sort_by(arr, compare)
+        ),
+    ]
+}
+
+// 按照名称查找排序算法
+pub fn find<T: Ord + Clone + 'static>(name: &str) -> Option<Box<dyn Sorter<T>>> {
+    registry().into_iter().find(|s| s.name() == name)
+}
+
+// 按照 parallel 的配置构造的并行 merge sort 和 quick sort
+pub fn parallel_registry<T: Ord + Clone + Send + 'static>(
+    parallel: Parallel,
+) -> Vec<Box<dyn Sorter<T>>> {
+    [
+        ("parallel merge sort", ParallelKind::Merge),
+        ("parallel quick sort", ParallelKind::Quick),
+    ]
+    .into_iter()
+    .map(|(name, kind)| -> Box<dyn Sorter<T>> {
+        Box::new(ParallelSorter {
+            name: format!("{} x{}", name, parallel.thread_count()),
+            kind,
+            parallel,
+        })
+    })
+    .collect()
+}
+
+pub fn run() {
+    let sorters = registry::<i32>();
+    println!(
+        "| {:<20} | {:<6} | {:<8} | {:<10} | {:<10} |",
+        "name", "stable", "in place", "worst", "average"
+    );
+    for sorter in sorters.iter() {
+        println!(
+            "| {:<20} | {:<6} | {:<8} | {:<10} | {:<10} |",
+            sorter.name(),
+            sorter.stable(),
+            sorter.in_place(),
+            sorter.worst_case().to_string(),
+            sorter.average_case().to_string()
+        );
+    }
+
+    // O(n^2) 的算法在 n = 100000 时需要执行几分钟, 因此只测试到 10000
+    // quick sort_v1 总是选择第一个元素作为pivot, 有序数组规模较大时栈会溢出
+    let mut report = Bench::new()
+        .sizes(&[100, 1000, 10000, 100000])
+        .distributions(&[Distribution::Random])
+        .max_quadratic(10000)
+        .run(&sorters);
+    let small = Bench::new()
+        .sizes(&[100, 1000, 10000])
+        .distributions(&[
+            Distribution::NearlyOrdered { swap_times: 10 },
+            Distribution::Duplicates { high: 10 },
+        ])
+        .run(&sorters);
+
+    // 规模较大时 quick sort_v1 和 sort_v2 会栈溢出, 只测试如下算法
+    let names = [
+        "merge sort_v1",
+        "merge sort_v2",
+        "heap sort_v3",
+        "quick sort_v3",
+        "quick sort_v4",
+        "intro sort",
+        "tim sort",
+    ];
+    let large: Vec<Box<dyn Sorter<i32>>> = names.iter().filter_map(|name| find(name)).collect();
+    let large = Bench::new()
+        .sizes(&[1000000])
+        .distributions(&[
+            Distribution::NearlyOrdered { swap_times: 100 },
+            Distribution::Duplicates { high: 10 },
+        ])
+        .warmup(0)
+        .trials(3)
+        .run(&large);
+    // 各种特殊形状的输入, 用于复现各个快速排序的最坏情况
+    // 例如 quick sort_v1 在逆序和 killer 输入上, quick sort_v2 在所有元素相同的输入上退化为 O(n^2)
+    let shapes = Bench::new()
+        .sizes(&[1000])
+        .distributions(&[
+            Distribution::Sorted,
+            Distribution::Reversed,
+            Distribution::AllEqual,
+            Distribution::FewUnique { k: 4 },
+            Distribution::Sawtooth { teeth: 8 },
+            Distribution::OrganPipe,
+            Distribution::Gaussian { std_dev: 50 },
+            Distribution::Zipf { k: 100 },
+            Distribution::Killer {
+                algorithm: "quick sort_v1",
+            },
+        ])
+        .run(&sorters);
+    // 并行排序在不同线程数下的耗时, 与对应的顺序算法比较
+    let mut scaling: Vec<Box<dyn Sorter<i32>>> = ["merge sort_v1", "quick sort_v4"]
+        .iter()
+        .filter_map(|name| find(name))
+        .collect();
+    for threads in [1, 2, 4, 8] {
+        scaling.extend(parallel_registry(
+            Parallel::new().threads(threads).threshold(1 << 14),
+        ));
+    }
+    let scaling = Bench::new()
+        .sizes(&[1000000])
+        .distributions(&[Distribution::Random])
+        .warmup(0)
+        .trials(3)
+        .run(&scaling);
+    report.extend(small);
+    report.extend(large);
+    report.extend(shapes);
+    report.extend(scaling);
+
+    // 不基于比较的排序与 O(n log n) 的排序对比, 使用相同的输入
+    let n = 1000000;
+    let bench = Bench::new().warmup(0).trials(3);
+    let input = Distribution::Random.generate(n);
+    let linear = bench.run_on(
+        &Distribution::Random.name(),
+        &input,
+        &[
+            ("counting sort", counting::sort),
+            ("lsd radix sort", radix::lsd_sort),
+            ("msd radix sort", radix::msd_sort),
+            ("intro sort", intro::sort),
+            ("quick sort_v4", quick::sort_v4),
+        ],
+    );
+    report.extend(linear);
+    let mut rng = random::rng();
+    let floats: Vec<f64> = (0..n).map(|_| rng.gen()).collect();
+    let floats = bench.run_on(
+        "uniform [0,1)",
+        &floats,
+        &[
+            ("bucket sort", bucket::sort),
+            ("intro sort", |arr| {
+                intro::sort_by(arr, |a, b| a.total_cmp(b))
+            }),
+        ],
+    );
+    report.extend(floats);
+
+    // 字符串排序, 使用 bible.txt 中的单词
+    let mut words: Vec<String> = Vec::new();
+    match util::read_file(Path::new("./src/files/bible.txt"), &mut words) {
+        Ok(_) => {
+            let strings = bench.run_on(
+                "bible words",
+                &words,
+                &[
+                    ("string quick3 sort", string::quick3_sort),
+                    ("string msd sort", string::msd_sort),
+                    ("merge sort_v1", merge::sort_v1),
+                    ("intro sort", intro::sort),
+                    ("tim sort", tim::sort),
+                ],
+            );
+            report.extend(strings);
+            let slices: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+            let slices = bench.run_on(
+                "bible words (&str)",
+                &slices,
+                &[
+                    ("string quick3 sort", string::quick3_sort),
+                    ("string msd sort", string::msd_sort),
+                    ("intro sort", intro::sort),
+                ],
+            );
+            report.extend(slices);
+        }
+        Err(e) => println!("{}", e),
+    }
+
+    print!("{}", report.render(Format::Markdown));
+
+    // 每种输入上中位数最小的算法
+    let mut fastest: Vec<&Measurement> = Vec::new();
+    for r in report.rows() {
+        match fastest
+            .iter_mut()
+            .find(|f| f.distribution == r.distribution && f.n == r.n)
+        {
+            Some(f) if r.median < f.median => *f = r,
+            Some(_) => (),
+            None => fastest.push(r),
+        }
+    }
+    for f in fastest {
+        println!("fastest on {} n = {}: {}", f.distribution, f.n, f.algorithm);
+    }
+    for (file, format) in [
+        ("sorting.csv", Format::Csv),
+        ("sorting.json", Format::Json),
+        ("sorting.md", Format::Markdown),
+    ] {
+        let path = Path::new("./target/bench").join(file);
+        if let Err(e) = report.save(&path, format) {
+            println!("failed to save {}: {}", path.display(), e);
+        }
+    }
+
+    let text = "it was the best of times it was the worst of times";
+    let sa = string::suffix_array(text);
+    println!("suffix array of {:?}:", text);
+    for &i in sa.iter().take(5) {
+        println!("{:>3} {}", i, &text[i..]);
+    }
+
+    // 以下只检查 sort_by 和 sort_by_key 的正确性, 不统计运行时间
+    let n = 10000;
+    let arr = util::generate_random_array(n, 1, n);
+    let mut checked = 0;
+    // threshold 较小, 保证并行排序确实创建了线程
+    let forked = parallel_registry(Parallel::new().threads(4).threshold(1000));
+    for sorter in sorters.iter().chain(forked.iter()) {
+        util::test_sort_by(
+            sorter.name(),
+            |arr| sorter.sort_by(arr, &|a, b| b.cmp(a)),
+            &mut arr.clone(),
+            |a, b| b.cmp(a),
+        );
+        checked += 1;
+    }
+    println!("sort by, descending order: {} sorters passed", checked);
+
+    // 默认配置的并行排序
+    let defaults: Vec<(&str, SortFn<i32>)> = vec![
+        ("parallel merge sort", parallel::merge_sort),
+        ("parallel quick sort", parallel::quick_sort),
+    ];
+    for (name, f) in defaults {
+        util::test_sort_by(name, f, &mut arr.clone(), i32::cmp);
+    }
+
+    // sort_by_key 是泛型函数, 不能放在 Sorter 中, 这里逐个测试
+    let by_key: Vec<(&str, SortFn<i32>)> = vec![
+        ("selection sort_by_key", |arr| {
+            selection::sort_by_key(arr, |&x| Reverse(x))
+        }),
+        ("bubble sort_v1_by_key", |arr| {
+            bubble::sort_v1_by_key(arr, |&x| Reverse(x))
+        }),
+        ("insertion sort_v1_by_key", |arr| {
+            insertion::sort_v1_by_key(arr, |&x| Reverse(x))
+        }),
+        ("insertion sort_v2_by_key", |arr| {
+            insertion::sort_v2_by_key(arr, |&x| Reverse(x))
+        }),
+        ("insertion sort_v3_by_key", |arr| {
+            insertion::sort_v3_by_key(arr, 0, arr.len() - 1, |&x| Reverse(x))
+        }),
+        ("merge sort_v1_by_key", |arr| {
+            merge::sort_v1_by_key(arr, |&x| Reverse(x))
+        }),
+        ("merge sort_v2_by_key", |arr| {
+            merge::sort_v2_by_key(arr, |&x| Reverse(x))
+        }),
+        ("heap sort_v1_by_key", |arr| {
+            heap::sort_v1_by_key(arr, |&x| Reverse(x))
+        }),
+        ("heap sort_v2_by_key", |arr| {
+            heap::sort_v2_by_key(arr, |&x| Reverse(x))
+        }),
+        ("heap sort_v3_by_key", |arr| {
+            heap::sort_v3_by_key(arr, |&x| Reverse(x))
+        }),
+        ("quick sort_v1_by_key", |arr| {
+            quick::sort_v1_by_key(arr, |&x| Reverse(x))
+        }),
+        ("quick sort_v2_by_key", |arr| {
+            quick::sort_v2_by_key(arr, |&x| Reverse(x))
+        }),
+        ("quick sort_v3_by_key", |arr| {
+            quick::sort_v3_by_key(arr, |&x| Reverse(x))
+        }),
+        ("quick sort_v4_by_key", |arr| {
+            quick::sort_v4_by_key(arr, |&x| Reverse(x))
+        }),
+        ("intro sort_by_key", |arr| {
+            intro::sort_by_key(arr, |&x| Reverse(x))
+        }),
+        ("tim sort_by_key", |arr| {
+            tim::sort_by_key(arr, |&x| Reverse(x))
+        }),
+        ("parallel merge sort_by_key", |arr| {
+            parallel::merge_sort_by_key(arr, |&x| Reverse(x))
+        }),
+        ("parallel quick sort_by_key", |arr| {
+            parallel::quick_sort_by_key(arr, |&x| Reverse(x))
+        }),
+    ];
+    let n = 10000;
+    let arr = util::generate_random_array(n, 1, n);
+    for (name, f) in by_key.iter() {
+        util::test_sort_by(name, f, &mut arr.clone(), |a, b| b.cmp(a));
+    }
+    println!(
+        "sort by key, descending order: {} functions passed",
+        by_key.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::random;
+    use rand::Rng;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use std::thread;
+
+    #[test]
+    fn metadata() {
+        let sorters = registry::<i32>();
+        let names: HashSet<&str> = sorters.iter().map(|s| s.name()).collect();
+        assert_eq!(names.len(), sorters.len());
+
+        let merge = find::<i32>("merge sort_v1").unwrap();
+        assert!(merge.stable() && !merge.in_place());
+        assert_eq!(merge.worst_case(), Complexity::Linearithmic);
+        assert_eq!(
+            find::<i32>("quick sort_v4")
+                .unwrap()
+                .worst_case()
+                .to_string(),
+            "O(n^2)"
+        );
+        assert!(find::<i32>("bogo sort").is_none());
+
+        // 并行排序不在 registry 中, 名称中包含线程数
+        assert!(find::<i32>("parallel merge sort").is_none());
+        let parallel = parallel_registry::<i32>(Parallel::new().threads(4));
+        let names: Vec<&str> = parallel.iter().map(|s| s.name()).collect();
+        assert_eq!(names, ["parallel merge sort x4", "parallel quick sort x4"]);
+        assert!(parallel[0].stable() && !parallel[1].stable());
+    }
+
+    #[test]
+    fn parallel_forks() {
+        // 比较函数在多个线程中被调用, 说明 sort_by 确实使用了并行排序
+        let arr: Vec<i32> = (0..2000).rev().collect();
+        for sorter in parallel_registry::<i32>(Parallel::new().threads(4).threshold(100)) {
+            let callers = Mutex::new(HashSet::new());
+            let mut res = arr.clone();
+            sorter.sort_by(&mut res, &|a, b| {
+                callers.lock().unwrap().insert(thread::current().id());
+                a.cmp(b)
+            });
+            assert!(util::is_sorted(&res), "{}", sorter.name());
+            assert!(callers.into_inner().unwrap().len() > 1, "{}", sorter.name());
+        }
+    }
+
+    // threshold 小于测试的规模, 并行排序会真正创建线程
+    fn with_parallel<T: Ord + Clone + Send + 'static>() -> Vec<Box<dyn Sorter<T>>> {
+        let mut sorters = registry();
+        for threads in [2, 3] {
+            sorters.extend(parallel_registry(
+                Parallel::new().threads(threads).threshold(16),
+            ));
+        }
+        sorters
+    }
+
+    #[test]
+    fn all_sorters() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for sorter in with_parallel::<i32>() {
+                for n in [0, 1, 2, 17, 100, 500] {
+                    let arr: Vec<i32> = (0..n).map(|_| rng.gen_range(-20, 20)).collect();
+                    let mut expected = arr.clone();
+                    expected.sort();
+
+                    let mut res = arr.clone();
+                    sorter.sort(&mut res);
+                    assert_eq!(res, expected, "{}", sorter.name());
+
+                    let mut res = arr.clone();
+                    sorter.sort_by(&mut res, &|a, b| b.cmp(a));
+                    expected.reverse();
+                    assert_eq!(res, expected, "{}", sorter.name());
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn stability() {
+        random::reproducible(|| {
+            // 只按照第一个分量排序, 稳定的算法中第二个分量保持递增
+            let mut rng = random::rng();
+            let arr: Vec<(i32, usize)> = (0..300).map(|i| (rng.gen_range(0, 10), i)).collect();
+            for sorter in with_parallel::<(i32, usize)>() {
+                if !sorter.stable() {
+                    continue;
+                }
+                let mut res = arr.clone();
+                sorter.sort_by(&mut res, &|a, b| a.0.cmp(&b.0));
+                let mut expected = arr.clone();
+                expected.sort_by_key(|p| p.0);
+                assert_eq!(res, expected, "{}", sorter.name());
+            }
+        });
+    }
+}
@@ -0,0 +1,263 @@
+use super::intro;
+use std::cmp::Ordering;
+use std::mem;
+
+// 字符串排序, 按照 UTF-8 字节逐个比较, 结果与 String 的 Ord 一致
+// 适用于 &mut [String] 和 &mut [&str] 等实现了 AsRef<str> 的类型
+// 递归处理第 d 个字节时, 子数组中的字符串前 d 个字节都相同, 只需要比较剩余的部分
+
+// 规模不超过 CUTOFF 时使用 insertion sort
+const CUTOFF: usize = 15;
+
+// 第 d 个字节加 1, 0 表示字符串已经结束, 结束的字符串排在前面
+fn char_at<T: AsRef<str>>(s: &T, d: usize) -> usize {
+    match s.as_ref().as_bytes().get(d) {
+        Some(&b) => b as usize + 1,
+        None => 0,
+    }
+}
+
+// 从第 d 个字节开始比较的 insertion sort
+fn insertion_sort<T: AsRef<str>>(arr: &mut [T], d: usize) {
+    for i in 1..arr.len() {
+        let mut j = i;
+        while j > 0 && arr[j].as_ref().as_bytes()[d..] < arr[j - 1].as_ref().as_bytes()[d..] {
+            arr.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+// 3-ways string quick sort (Bentley-Sedgewick)
+// 按照第 d 个字节分为 < v, == v, > v 三部分, == v 的部分继续比较第 d + 1 个字节
+// 较小的两部分递归排序, 最大的部分在循环中继续处理, 每次递归规模至少减半
+// 递归深度为 O(log n), 与相同前缀的长度无关
+fn quick3<T: AsRef<str>>(mut arr: &mut [T], mut d: usize) {
+    loop {
+        if arr.len() <= CUTOFF {
+            insertion_sort(arr, d);
+            return;
+        }
+
+        // 选择中间的元素作为 pivot, 对有序的输入同样有效
+        let mid = arr.len() / 2;
+        arr.swap(0, mid);
+        let v = char_at(&arr[0], d);
+
+        // arr[0:lt) < v, arr[lt:i) == v, arr(gt:len) > v
+        let mut lt = 0;
+        let mut gt = arr.len() - 1;
+        let mut i = 1;
+        while i <= gt {
+            let t = char_at(&arr[i], d);
+            match t.cmp(&v) {
+                Ordering::Less => {
+                    arr.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    arr.swap(i, gt);
+                    gt -= 1;
+                }
+                Ordering::Equal => i += 1,
+            }
+        }
+
+        let (less, rest) = mem::take(&mut arr).split_at_mut(lt);
+        let (equal, greater) = rest.split_at_mut(gt + 1 - lt);
+        // v 为 0 时这部分字符串完全相同, 不需要继续排序
+        let equal: &mut [T] = if v > 0 { equal } else { &mut [] };
+
+        let mut parts = [(less, d), (equal, d + 1), (greater, d)];
+        parts.sort_by_key(|(part, _)| part.len());
+        let [(a, da), (b, db), (c, dc)] = parts;
+        quick3(a, da);
+        quick3(b, db);
+        arr = c;
+        d = dc;
+    }
+}
+
+pub fn quick3_sort<T: AsRef<str>>(arr: &mut [T]) {
+    quick3(arr, 0);
+}
+
+// MSD string radix sort, 按照第 d 个字节分到 257 个桶中
+// 使用 American flag sort 的方式在原数组中交换元素, 不需要辅助数组, 也不需要 Clone
+// 与 quick3 相同, 最大的桶在循环中继续处理, 递归深度为 O(log n)
+fn msd<T: AsRef<str>>(mut arr: &mut [T], mut d: usize) {
+    loop {
+        if arr.len() <= CUTOFF {
+            insertion_sort(arr, d);
+            return;
+        }
+
+        let mut count = [0; 258];
+        for s in arr.iter() {
+            count[char_at(s, d) + 1] += 1;
+        }
+        for r in 0..257 {
+            count[r + 1] += count[r];
+        }
+
+        // head[r] 为第 r 个桶中下一个待确定的位置, 桶的范围为 [count[r], count[r+1])
+        let mut head = count;
+        for r in 0..257 {
+            while head[r] < count[r + 1] {
+                let c = char_at(&arr[head[r]], d);
+                if c == r {
+                    head[r] += 1;
+                } else {
+                    arr.swap(head[r], head[c]);
+                    head[c] += 1;
+                }
+            }
+        }
+
+        // 第 0 个桶中的字符串已经结束, 不需要继续排序
+        let largest = (1..257).max_by_key(|&r| count[r + 1] - count[r]).unwrap();
+        for r in 1..257 {
+            if r != largest {
+                msd(&mut arr[count[r]..count[r + 1]], d + 1);
+            }
+        }
+        arr = &mut mem::take(&mut arr)[count[largest]..count[largest + 1]];
+        d += 1;
+    }
+}
+
+pub fn msd_sort<T: AsRef<str>>(arr: &mut [T]) {
+    msd(arr, 0);
+}
+
+// 后缀数组, 返回 s 的所有后缀按照字典序排列后的起始位置
+// 只包含字符边界上的后缀, UTF-8 的字节序与字符序一致, 因此可以按照字符比较
+// 使用倍增法: 已知所有后缀前 k 个字符的排名, 后缀 i 前 2k 个字符的排名
+// 由 (rank[i], rank[i + k]) 决定, 排序 O(log n) 轮, 与重复子串的长度无关
+pub fn suffix_array(s: &str) -> Vec<usize> {
+    let starts: Vec<usize> = s.char_indices().map(|(i, _)| i).collect();
+    let n = starts.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut sa: Vec<usize> = (0..n).collect();
+    let mut rank: Vec<usize> = s.chars().map(|c| c as usize).collect();
+    let mut next = vec![0; n];
+    let mut k = 1;
+    loop {
+        // 后缀 i + k 不存在时排在最前面
+        let key = |i: usize| (rank[i], if i + k < n { rank[i + k] + 1 } else { 0 });
+        intro::sort_by_key(&mut sa, |&i| key(i));
+        next[sa[0]] = 0;
+        for j in 1..n {
+            next[sa[j]] = next[sa[j - 1]] + (key(sa[j - 1]) < key(sa[j])) as usize;
+        }
+        mem::swap(&mut rank, &mut next);
+        // 所有的排名都不相同时已经有序
+        if rank[sa[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+
+    sa.into_iter().map(|i| starts[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{self, random};
+    use rand::Rng;
+    use std::path::Path;
+
+    fn check<T: AsRef<str> + Ord + Clone + std::fmt::Debug>(arr: &[T]) {
+        let mut expected = arr.to_vec();
+        expected.sort();
+        let mut res = arr.to_vec();
+        quick3_sort(&mut res);
+        assert!(util::is_sorted(&res));
+        assert_eq!(res, expected);
+        let mut res = arr.to_vec();
+        msd_sort(&mut res);
+        assert!(util::is_sorted(&res));
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn strings() {
+        let words =
+            "she sells sea shells by the sea shore the shells she sells are surely seashells";
+        let mut res: Vec<&str> = words.split(' ').collect();
+        msd_sort(&mut res);
+        assert_eq!(res[..4], ["are", "by", "sea", "sea"]);
+        check(&words.split(' ').collect::<Vec<&str>>());
+        check(&words.split(' ').map(String::from).collect::<Vec<String>>());
+
+        // 空字符串, 前缀, 非 ASCII 字符
+        check(&[
+            "", "a", "", "ab", "a", "abc", "b", "é", "z", "中文", "中", "",
+        ]);
+        check::<String>(&[]);
+        check(&vec!["same"; 100]);
+    }
+
+    #[test]
+    fn random_strings() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for n in [10, 100, 2000] {
+                // 字母表较小, 有大量相同的前缀
+                let arr: Vec<String> = (0..n)
+                    .map(|_| {
+                        let len = rng.gen_range(0, 12);
+                        (0..len)
+                            .map(|_| rng.gen_range(b'a', b'e') as char)
+                            .collect()
+                    })
+                    .collect();
+                check(&arr);
+            }
+        });
+    }
+
+    #[test]
+    fn bible() {
+        let mut words: Vec<String> = Vec::new();
+        util::read_file(Path::new("./src/files/bible.txt"), &mut words).unwrap();
+        words.truncate(20000);
+        check(&words);
+    }
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(suffix_array("banana"), vec![5, 3, 1, 0, 4, 2]);
+        assert!(suffix_array("").is_empty());
+
+        let s = "abracadabra 中文中文";
+        let sa = suffix_array(s);
+        assert_eq!(sa.len(), s.chars().count());
+        assert!(sa.windows(2).all(|p| s[p[0]..] < s[p[1]..]));
+    }
+
+    #[test]
+    fn repetitive() {
+        // 很长的重复子串, 递归深度不能与相同前缀的长度相关
+        for s in ["ab".repeat(10000), "a".repeat(50000), "abc中".repeat(5000)] {
+            let sa = suffix_array(&s);
+            assert_eq!(sa.len(), s.chars().count());
+            assert!(sa.windows(2).all(|p| s[p[0]..] < s[p[1]..]));
+        }
+        assert_eq!(suffix_array(&"a".repeat(5)), vec![4, 3, 2, 1, 0]);
+
+        // 大量字符串有很长的相同前缀
+        let prefix = "x".repeat(20000);
+        let arr: Vec<String> = (0..100)
+            .map(|i| format!("{}{}", prefix, (i * 37) % 100))
+            .collect();
+        check(&arr);
+        let s = "ab".repeat(1000);
+        check(&(0..s.len()).map(|i| &s[i..]).collect::<Vec<&str>>());
+    }
+}
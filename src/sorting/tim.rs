@@ -0,0 +1,502 @@
+use std::cmp::Ordering;
+
+// TimSort: 稳定的自适应归并排序
+// 1. 从左到右寻找已经有序的部分(run), 严格递减的 run 直接翻转
+// 2. run 的长度小于 min_run 时使用 binary insertion sort 扩展到 min_run
+// 3. run 依次入栈, 保持栈中 run 的长度满足以下条件, 使得每次 merge 的两部分长度接近
+//    len[i-2] > len[i-1] + len[i], len[i-1] > len[i]
+// 4. merge 时一边连续胜出 min_gallop 次后进入 galloping 模式, 使用指数搜索批量复制
+// 已经有序的数组只有一个 run, 只需要 n - 1 次比较
+
+// 长度小于 MIN_MERGE 时只使用 binary insertion sort
+const MIN_MERGE: usize = 64;
+// 进入 galloping 模式的初始阈值
+const MIN_GALLOP: usize = 7;
+
+// 计算 min_run, 使 n / min_run 等于或者略小于 2 的幂, 最后的 merge 更加均衡
+fn min_run_length(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= MIN_MERGE {
+        r |= n & 1;
+        n >>= 1;
+    }
+    n + r
+}
+
+// 返回从 arr[0] 开始的 run 的长度, 严格递减的 run 翻转为递增
+// 递减必须是严格的, 否则翻转会改变相等元素的顺序
+fn count_run_and_make_ascending<T, F>(arr: &mut [T], compare: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    if len < 2 {
+        return len;
+    }
+
+    let mut end = 2;
+    if compare(&arr[1], &arr[0]) == Ordering::Less {
+        while end < len && compare(&arr[end], &arr[end - 1]) == Ordering::Less {
+            end += 1;
+        }
+        arr[..end].reverse();
+    } else {
+        while end < len && compare(&arr[end], &arr[end - 1]) != Ordering::Less {
+            end += 1;
+        }
+    }
+    end
+}
+
+// arr[..start] 已经有序, 使用二分查找把 arr[start..] 依次插入
+// 插入到相等元素的后面, 保证稳定
+fn binary_sort<T, F>(arr: &mut [T], start: usize, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for i in start.max(1)..arr.len() {
+        let mut low = 0;
+        let mut high = i;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if compare(&arr[i], &arr[mid]) == Ordering::Less {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        arr[low..=i].rotate_right(1);
+    }
+}
+
+// 在有序的 arr 中从 hint 开始指数搜索, 再在找到的区间内二分查找
+// before(x) 为 true 的元素都在 false 的元素之前, 返回第一个 before(x) 为 false 的位置
+fn gallop<T, P>(arr: &[T], hint: usize, mut before: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    let len = arr.len();
+    let (mut low, mut high);
+    if before(&arr[hint]) {
+        // 向右搜索, 依次检查 hint + 1, hint + 3, hint + 7, ...
+        let (mut last, mut offset) = (0, 1);
+        while hint + offset < len && before(&arr[hint + offset]) {
+            last = offset;
+            offset = 2 * offset + 1;
+        }
+        low = hint + last + 1;
+        high = (hint + offset).min(len);
+    } else {
+        // 向左搜索, 依次检查 hint - 1, hint - 3, hint - 7, ...
+        let (mut last, mut offset) = (0, 1);
+        while offset <= hint && !before(&arr[hint - offset]) {
+            last = offset;
+            offset = 2 * offset + 1;
+        }
+        low = if offset > hint { 0 } else { hint - offset + 1 };
+        high = hint - last;
+    }
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if before(&arr[mid]) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+struct TimSort {
+    // 待 merge 的 run, (起始位置, 长度)
+    runs: Vec<(usize, usize)>,
+    min_gallop: usize,
+}
+
+impl TimSort {
+    fn new() -> Self {
+        Self {
+            runs: Vec::new(),
+            min_gallop: MIN_GALLOP,
+        }
+    }
+
+    // 恢复栈的不变式
+    // 除了检查栈顶的 3 个 run 之外还要检查第 4 个, 否则不变式可能被破坏
+    fn merge_collapse<T, F>(&mut self, arr: &mut [T], compare: &mut F)
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        while self.runs.len() > 1 {
+            let len = |i: usize| self.runs[i].1;
+            let mut n = self.runs.len() - 2;
+            if (n > 0 && len(n - 1) <= len(n) + len(n + 1))
+                || (n > 1 && len(n - 2) <= len(n - 1) + len(n))
+            {
+                if len(n - 1) < len(n + 1) {
+                    n -= 1;
+                }
+            } else if len(n) > len(n + 1) {
+                break;
+            }
+            self.merge_at(arr, n, compare);
+        }
+    }
+
+    // 所有 run 都已入栈, 从栈顶开始全部 merge
+    fn merge_force_collapse<T, F>(&mut self, arr: &mut [T], compare: &mut F)
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        while self.runs.len() > 1 {
+            let mut n = self.runs.len() - 2;
+            if n > 0 && self.runs[n - 1].1 < self.runs[n + 1].1 {
+                n -= 1;
+            }
+            self.merge_at(arr, n, compare);
+        }
+    }
+
+    // merge 栈中相邻的第 i 和 i + 1 个 run
+    fn merge_at<T, F>(&mut self, arr: &mut [T], i: usize, compare: &mut F)
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let (mut base1, mut len1) = self.runs[i];
+        let (base2, mut len2) = self.runs[i + 1];
+        self.runs[i] = (base1, len1 + len2);
+        self.runs.remove(i + 1);
+
+        // run1 中 <= run2[0] 的元素已经在最终的位置上
+        let k = gallop(&arr[base1..base1 + len1], 0, |x| {
+            compare(&arr[base2], x) != Ordering::Less
+        });
+        base1 += k;
+        len1 -= k;
+        if len1 == 0 {
+            return;
+        }
+
+        // run2 中 >= run1 最后一个元素的元素已经在最终的位置上
+        let last = base1 + len1 - 1;
+        len2 = gallop(&arr[base2..base2 + len2], len2 - 1, |x| {
+            compare(x, &arr[last]) == Ordering::Less
+        });
+        if len2 == 0 {
+            return;
+        }
+
+        // 把较短的 run 复制到辅助数组
+        if len1 <= len2 {
+            self.merge_lo(arr, base1, len1, len2, compare);
+        } else {
+            self.merge_hi(arr, base1, len1, len2, compare);
+        }
+    }
+
+    // 从左向右 merge, run1 = arr[base..base+len1] 复制到 tmp
+    fn merge_lo<T, F>(
+        &mut self,
+        arr: &mut [T],
+        base: usize,
+        len1: usize,
+        len2: usize,
+        compare: &mut F,
+    ) where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let tmp = arr[base..base + len1].to_vec();
+        // tmp[i..] 和 arr[j..end] 为待 merge 的部分, arr[..dest] 已经完成
+        let (mut i, mut j, mut dest) = (0, base + len1, base);
+        let end = base + len1 + len2;
+
+        'outer: loop {
+            // 逐个比较, 记录两边连续胜出的次数
+            let (mut count1, mut count2) = (0, 0);
+            loop {
+                if compare(&arr[j], &tmp[i]) == Ordering::Less {
+                    arr[dest] = arr[j].clone();
+                    j += 1;
+                    count1 = 0;
+                    count2 += 1;
+                } else {
+                    arr[dest] = tmp[i].clone();
+                    i += 1;
+                    count1 += 1;
+                    count2 = 0;
+                }
+                dest += 1;
+                if i == len1 || j == end {
+                    break 'outer;
+                }
+                if count1 >= self.min_gallop || count2 >= self.min_gallop {
+                    break;
+                }
+            }
+
+            // galloping 模式, 批量复制直到两边每次复制的元素都少于 MIN_GALLOP
+            loop {
+                // tmp 中 <= arr[j] 的元素
+                count1 = gallop(&tmp[i..], 0, |x| compare(&arr[j], x) != Ordering::Less);
+                arr[dest..dest + count1].clone_from_slice(&tmp[i..i + count1]);
+                i += count1;
+                dest += count1;
+                if i == len1 {
+                    break 'outer;
+                }
+
+                // arr[j..end] 中 < tmp[i] 的元素
+                count2 = gallop(&arr[j..end], 0, |x| compare(x, &tmp[i]) == Ordering::Less);
+                for _ in 0..count2 {
+                    arr[dest] = arr[j].clone();
+                    dest += 1;
+                    j += 1;
+                }
+                if j == end {
+                    break 'outer;
+                }
+
+                // 持续 galloping 时降低进入的阈值
+                self.min_gallop = self.min_gallop.saturating_sub(1);
+                if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                    break;
+                }
+            }
+            // 离开 galloping 模式时提高阈值
+            self.min_gallop += 2;
+        }
+
+        // run2 剩余的元素已经在最终的位置上
+        arr[dest..dest + len1 - i].clone_from_slice(&tmp[i..]);
+    }
+
+    // 从右向左 merge, run2 = arr[base+len1..base+len1+len2] 复制到 tmp
+    fn merge_hi<T, F>(
+        &mut self,
+        arr: &mut [T],
+        base: usize,
+        len1: usize,
+        len2: usize,
+        compare: &mut F,
+    ) where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let tmp = arr[base + len1..base + len1 + len2].to_vec();
+        // arr[base..base+i] 和 tmp[..j] 为待 merge 的部分, arr[dest..] 已经完成
+        let (mut i, mut j) = (len1, len2);
+        let mut dest = base + len1 + len2;
+
+        'outer: loop {
+            let (mut count1, mut count2) = (0, 0);
+            loop {
+                dest -= 1;
+                // 相等时先放 run2 的元素, 保证稳定
+                if compare(&tmp[j - 1], &arr[base + i - 1]) == Ordering::Less {
+                    arr[dest] = arr[base + i - 1].clone();
+                    i -= 1;
+                    count1 += 1;
+                    count2 = 0;
+                } else {
+                    arr[dest] = tmp[j - 1].clone();
+                    j -= 1;
+                    count1 = 0;
+                    count2 += 1;
+                }
+                if i == 0 || j == 0 {
+                    break 'outer;
+                }
+                if count1 >= self.min_gallop || count2 >= self.min_gallop {
+                    break;
+                }
+            }
+
+            loop {
+                // run1 中 > tmp[j-1] 的元素
+                let k = gallop(&arr[base..base + i], i - 1, |x| {
+                    compare(&tmp[j - 1], x) != Ordering::Less
+                });
+                count1 = i - k;
+                for _ in 0..count1 {
+                    dest -= 1;
+                    i -= 1;
+                    arr[dest] = arr[base + i].clone();
+                }
+                if i == 0 {
+                    break 'outer;
+                }
+
+                // tmp 中 >= arr[base+i-1] 的元素
+                let last = &arr[base + i - 1];
+                let k = gallop(&tmp[..j], j - 1, |x| compare(x, last) == Ordering::Less);
+                count2 = j - k;
+                dest -= count2;
+                j = k;
+                arr[dest..dest + count2].clone_from_slice(&tmp[j..j + count2]);
+                if j == 0 {
+                    break 'outer;
+                }
+
+                self.min_gallop = self.min_gallop.saturating_sub(1);
+                if count1 < MIN_GALLOP && count2 < MIN_GALLOP {
+                    break;
+                }
+            }
+            self.min_gallop += 2;
+        }
+
+        // run1 剩余的元素已经在最终的位置上
+        arr[base..base + j].clone_from_slice(&tmp[..j]);
+    }
+}
+
+pub fn sort<T: Ord + Clone>(arr: &mut [T]) {
+    sort_by(arr, T::cmp);
+}
+
+pub fn sort_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let len = arr.len();
+    if len < 2 {
+        return;
+    }
+
+    // 规模较小时不需要 merge
+    if len < MIN_MERGE {
+        let run = count_run_and_make_ascending(arr, &mut compare);
+        binary_sort(arr, run, &mut compare);
+        return;
+    }
+
+    let min_run = min_run_length(len);
+    let mut ts = TimSort::new();
+    let mut low = 0;
+    while low < len {
+        let mut run = count_run_and_make_ascending(&mut arr[low..], &mut compare);
+        if run < min_run {
+            let force = min_run.min(len - low);
+            binary_sort(&mut arr[low..low + force], run, &mut compare);
+            run = force;
+        }
+
+        ts.runs.push((low, run));
+        ts.merge_collapse(arr, &mut compare);
+        low += run;
+    }
+    ts.merge_force_collapse(arr, &mut compare);
+}
+
+pub fn sort_by_key<T, K, F>(arr: &mut [T], mut f: F)
+where
+    T: Clone,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    sort_by(arr, |a, b| f(a).cmp(&f(b)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{generate, random};
+    use rand::Rng;
+
+    // 返回排序结果和比较次数
+    fn counted(arr: &[i32]) -> (Vec<i32>, usize) {
+        let mut calls = 0;
+        let mut res = arr.to_vec();
+        sort_by(&mut res, |a, b| {
+            calls += 1;
+            a.cmp(b)
+        });
+        (res, calls)
+    }
+
+    #[test]
+    fn integer() {
+        let mut res = vec![4, 1, 8, 5, 7];
+        sort(&mut res);
+        assert_eq!(res, vec![1, 4, 5, 7, 8]);
+
+        let mut res: Vec<i32> = vec![];
+        sort(&mut res);
+        assert!(res.is_empty());
+
+        assert_eq!(min_run_length(63), 63);
+        assert_eq!(min_run_length(64), 32);
+        assert_eq!(min_run_length(65), 33);
+        assert!((32..=64).contains(&min_run_length(1000000)));
+    }
+
+    #[test]
+    fn distributions() {
+        random::reproducible(|| {
+            let seed: u64 = random::rng().gen();
+            for n in [63, 64, 65, 100, 1000, 5000] {
+                let inputs: Vec<Vec<i32>> = vec![
+                    generate::uniform(n, 0, n as i64, seed),
+                    generate::few_unique(n, 3, seed),
+                    generate::nearly_sorted(n, 10, seed),
+                    generate::reversed(n),
+                    generate::sawtooth(n, 7),
+                    generate::organ_pipe(n),
+                    generate::zipf(n, 20, 1.0, seed),
+                ];
+                for arr in inputs {
+                    let mut expected = arr.clone();
+                    expected.sort();
+                    assert_eq!(counted(&arr).0, expected);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn adaptive() {
+        // 有序和严格逆序的数组只需要 n - 1 次比较
+        let n = 10000;
+        assert_eq!(counted(&generate::sorted(n)).1, n - 1);
+        assert_eq!(counted(&generate::reversed(n)).1, n - 1);
+
+        // 两个交错的有序部分, galloping 使比较次数远小于 n log n
+        let arr: Vec<i32> = (0..n as i32 / 2)
+            .map(|x| x * 2)
+            .chain((0..n as i32 / 2).map(|x| x * 2 + 1000))
+            .collect();
+        let (res, calls) = counted(&arr);
+        assert!(res.windows(2).all(|p| p[0] <= p[1]));
+        assert!(calls < 2 * n);
+    }
+
+    #[test]
+    fn stable() {
+        random::reproducible(|| {
+            let mut rng = random::rng();
+            for n in [10, 100, 1000, 5000] {
+                let arr: Vec<(i32, usize)> = (0..n).map(|i| (rng.gen_range(0, 10), i)).collect();
+                let mut res = arr.clone();
+                sort_by_key(&mut res, |p| p.0);
+                for pair in res.windows(2) {
+                    assert!(
+                        pair[0].0 < pair[1].0 || pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1
+                    );
+                }
+            }
+
+            // 降序排列, 并且包含较长的相等部分
+            let mut res: Vec<(i32, usize)> = (0..3000).map(|i| (i as i32 / 100, i)).collect();
+            sort_by(&mut res, |a, b| b.0.cmp(&a.0));
+            for pair in res.windows(2) {
+                assert!(pair[0].0 > pair[1].0 || pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1);
+            }
+        });
+    }
+}
@@ -1,5 +1,5 @@
 use crate::util;
-use rand;
+use crate::util::random;
 use rand::Rng;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
@@ -221,7 +221,7 @@ pub fn run() {
 
     // 测试前序遍历
     let mut bst: BST<usize, usize> = BST::new();
-    let mut rng = rand::thread_rng();
+    let mut rng = random::rng();
     let n = 10;
     let m = 100;
     for _ in 0..=n {
@@ -1,3 +1,7 @@
+use crate::util::random;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
 pub mod uf1 {
     pub struct UnionFind {
         id: Vec<usize>,
@@ -389,12 +393,11 @@ pub mod uf6 {
 }
 
 pub mod union_find_test_helper {
-    use rand::{self, Rng};
+    use rand::Rng;
     use std::time::Instant;
 
-    pub fn test_uf1(n: usize) {
+    pub fn test_uf1<R: Rng>(n: usize, rng: &mut R) {
         let mut uf = super::uf1::UnionFind::new(n);
-        let mut rng = rand::thread_rng();
 
         // 测试运行时间
         let now = Instant::now();
@@ -416,9 +419,8 @@ pub mod union_find_test_helper {
         println!("UF1, {} ops, {} µs", 2 * n, now.elapsed().as_micros());
     }
 
-    pub fn test_uf2(n: usize) {
+    pub fn test_uf2<R: Rng>(n: usize, rng: &mut R) {
         let mut uf = super::uf2::UnionFind::new(n);
-        let mut rng = rand::thread_rng();
 
         // 测试运行时间
         let now = Instant::now();
@@ -440,9 +442,8 @@ pub mod union_find_test_helper {
         println!("UF2, {} ops, {} µs", 2 * n, now.elapsed().as_micros());
     }
 
-    pub fn test_uf3(n: usize) {
+    pub fn test_uf3<R: Rng>(n: usize, rng: &mut R) {
         let mut uf = super::uf3::UnionFind::new(n);
-        let mut rng = rand::thread_rng();
 
         // 测试运行时间
         let now = Instant::now();
@@ -464,9 +465,8 @@ pub mod union_find_test_helper {
         println!("UF3, {} ops, {} µs", 2 * n, now.elapsed().as_micros());
     }
 
-    pub fn test_uf4(n: usize) {
+    pub fn test_uf4<R: Rng>(n: usize, rng: &mut R) {
         let mut uf = super::uf4::UnionFind::new(n);
-        let mut rng = rand::thread_rng();
 
         // 测试运行时间
         let now = Instant::now();
@@ -488,9 +488,8 @@ pub mod union_find_test_helper {
         println!("UF4, {} ops, {} µs", 2 * n, now.elapsed().as_micros());
     }
 
-    pub fn test_uf5(n: usize) {
+    pub fn test_uf5<R: Rng>(n: usize, rng: &mut R) {
         let mut uf = super::uf5::UnionFind::new(n);
-        let mut rng = rand::thread_rng();
 
         // 测试运行时间
         let now = Instant::now();
@@ -512,9 +511,8 @@ pub mod union_find_test_helper {
         println!("UF5, {} ops, {} µs", 2 * n, now.elapsed().as_micros());
     }
 
-    pub fn test_uf6(n: usize) {
+    pub fn test_uf6<R: Rng>(n: usize, rng: &mut R) {
         let mut uf = super::uf6::UnionFind::new(n);
-        let mut rng = rand::thread_rng();
 
         // 测试运行时间
         let now = Instant::now();
@@ -537,13 +535,15 @@ pub mod union_find_test_helper {
     }
 }
 
+// 每个实现的 rng 由同一个 seed 生成, 随机的操作序列完全相同, 运行时间可以直接比较
 pub fn run() {
     let n = 10000;
-
-    union_find_test_helper::test_uf1(n);
-    union_find_test_helper::test_uf2(n);
-    union_find_test_helper::test_uf3(n);
-    union_find_test_helper::test_uf4(n);
-    union_find_test_helper::test_uf5(n);
-    union_find_test_helper::test_uf6(n);
+    let seed: u64 = random::rng().gen();
+
+    union_find_test_helper::test_uf1(n, &mut StdRng::seed_from_u64(seed));
+    union_find_test_helper::test_uf2(n, &mut StdRng::seed_from_u64(seed));
+    union_find_test_helper::test_uf3(n, &mut StdRng::seed_from_u64(seed));
+    union_find_test_helper::test_uf4(n, &mut StdRng::seed_from_u64(seed));
+    union_find_test_helper::test_uf5(n, &mut StdRng::seed_from_u64(seed));
+    union_find_test_helper::test_uf6(n, &mut StdRng::seed_from_u64(seed));
 }
@@ -0,0 +1,275 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+// 可以复现的测试数据, 随机的生成器都接受 seed, 相同的 seed 生成相同的数组
+// 生成的值超出目标类型的范围时取该类型的最小值或者最大值
+
+// 由 i64 转换为各种整数类型
+pub trait FromI64: Copy {
+    fn from_i64(v: i64) -> Self;
+}
+
+macro_rules! from_i64 {
+    ($($t:ty),*) => {
+        $(
+            impl FromI64 for $t {
+                fn from_i64(v: i64) -> Self {
+                    <$t>::try_from(v).unwrap_or(if v < 0 { <$t>::MIN } else { <$t>::MAX })
+                }
+            }
+        )*
+    };
+}
+
+from_i64!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+fn convert<T: FromI64>(values: impl Iterator<Item = i64>) -> Vec<T> {
+    values.map(T::from_i64).collect()
+}
+
+// [low, high] 之间均匀分布的随机数
+pub fn uniform<T: FromI64>(n: usize, low: i64, high: i64, seed: u64) -> Vec<T> {
+    assert!(low <= high);
+    let mut rng = StdRng::seed_from_u64(seed);
+    convert((0..n).map(|_| rng.gen_range(low, high + 1)))
+}
+
+// 0, 1, ..., n-1
+pub fn sorted<T: FromI64>(n: usize) -> Vec<T> {
+    convert(0..n as i64)
+}
+
+// n-1, n-2, ..., 0
+pub fn reversed<T: FromI64>(n: usize) -> Vec<T> {
+    convert((0..n as i64).rev())
+}
+
+// 有序数组中随机交换 swap_times 对元素
+pub fn nearly_sorted<T: FromI64>(n: usize, swap_times: usize, seed: u64) -> Vec<T> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut ret: Vec<T> = sorted(n);
+    if n > 0 {
+        for _ in 0..swap_times {
+            ret.swap(rng.gen_range(0, n), rng.gen_range(0, n));
+        }
+    }
+    ret
+}
+
+// 所有元素都等于 value
+pub fn all_equal<T: FromI64>(n: usize, value: i64) -> Vec<T> {
+    vec![T::from_i64(value); n]
+}
+
+// 只有 k 个不同的值 0..k
+pub fn few_unique<T: FromI64>(n: usize, k: usize, seed: u64) -> Vec<T> {
+    assert!(k > 0);
+    uniform(n, 0, k as i64 - 1, seed)
+}
+
+// 锯齿形, 由 teeth 段递增序列组成, 例如 0 1 2 0 1 2 0 1 2
+pub fn sawtooth<T: FromI64>(n: usize, teeth: usize) -> Vec<T> {
+    assert!(teeth > 0);
+    let period = n.div_ceil(teeth).max(1);
+    convert((0..n).map(|i| (i % period) as i64))
+}
+
+// 管风琴形, 前半部分递增后半部分递减, 例如 0 1 2 3 2 1 0
+pub fn organ_pipe<T: FromI64>(n: usize) -> Vec<T> {
+    convert((0..n).map(|i| i.min(n - 1 - i) as i64))
+}
+
+// 正态分布, 使用 Box-Muller 变换生成后四舍五入
+pub fn gaussian<T: FromI64>(n: usize, mean: f64, std_dev: f64, seed: u64) -> Vec<T> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    convert((0..n).map(|_| {
+        // u1 在 (0, 1] 之间, 避免 ln(0)
+        let u1: f64 = 1.0 - rng.gen::<f64>();
+        let u2: f64 = rng.gen();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        (mean + std_dev * z).round() as i64
+    }))
+}
+
+// Zipf 分布, 取值为 1..=k, 取值为 r 的概率与 1 / r^s 成正比
+pub fn zipf<T: FromI64>(n: usize, k: usize, s: f64, seed: u64) -> Vec<T> {
+    assert!(k > 0);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // cumulative[r - 1] 为取值不超过 r 的权重之和
+    let mut cumulative = Vec::with_capacity(k);
+    let mut total = 0.0;
+    for r in 1..=k {
+        total += 1.0 / (r as f64).powf(s);
+        cumulative.push(total);
+    }
+
+    convert((0..n).map(|_| {
+        let u = rng.gen::<f64>() * total;
+        let r = cumulative.partition_point(|&c| c <= u).min(k - 1);
+        r as i64 + 1
+    }))
+}
+
+// McIlroy 的快速排序对抗算法 (A Killer Adversary for Quicksort)
+// 所有元素一开始都是未定值(gas), 比较两个 gas 时把其中一个冻结为当前最小的确定值
+// 尽量让被比较最多次的元素(通常是 pivot)保持 gas, 使 pivot 总是剩余元素中最大的
+// sort 对 0..n 的下标排序, 必须使用传入的比较函数, 并且是确定性的算法
+// 返回的数组用同样的算法排序时会退化为 O(n^2), 对三数取中等选择 pivot 的方法同样有效
+// 比较函数是 Send 的, 需要时可以放在 Mutex 中给要求 Fn + Sync 的排序使用
+pub fn quicksort_killer<T, F>(n: usize, sort: F) -> Vec<T>
+where
+    T: FromI64,
+    F: FnOnce(&mut [usize], &mut (dyn FnMut(&usize, &usize) -> Ordering + Send)),
+{
+    let gas = n as i64;
+    let mut values = vec![gas; n];
+    let mut solid = 0;
+    let mut candidate = 0;
+
+    let mut indexes: Vec<usize> = (0..n).collect();
+    {
+        let mut compare = |&x: &usize, &y: &usize| {
+            if values[x] == gas && values[y] == gas {
+                let frozen = if x == candidate { x } else { y };
+                values[frozen] = solid;
+                solid += 1;
+            }
+            if values[x] == gas {
+                candidate = x;
+            } else if values[y] == gas {
+                candidate = y;
+            }
+            values[x].cmp(&values[y])
+        };
+        sort(&mut indexes, &mut compare);
+    }
+
+    // 排序中没有被冻结的元素依次取剩余的值
+    for v in values.iter_mut().filter(|v| **v == gas) {
+        *v = solid;
+        solid += 1;
+    }
+    convert(values.into_iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorting::{merge, quick};
+
+    type SortBy = fn(&mut [usize], &mut (dyn FnMut(&usize, &usize) -> Ordering + Send));
+
+    #[test]
+    fn deterministic() {
+        let a: Vec<i32> = uniform(100, -5, 5, 7);
+        assert_eq!(a, uniform::<i32>(100, -5, 5, 7));
+        assert_ne!(a, uniform::<i32>(100, -5, 5, 8));
+        assert!(a.iter().all(|&x| (-5..=5).contains(&x)));
+
+        let g: Vec<i64> = gaussian(1000, 100.0, 10.0, 1);
+        assert_eq!(g, gaussian::<i64>(1000, 100.0, 10.0, 1));
+        let mean = g.iter().sum::<i64>() as f64 / 1000.0;
+        assert!((mean - 100.0).abs() < 2.0);
+
+        let z: Vec<u16> = zipf(1000, 10, 1.0, 3);
+        assert_eq!(z, zipf::<u16>(1000, 10, 1.0, 3));
+        assert!(z.iter().all(|&x| (1..=10).contains(&x)));
+        let ones = z.iter().filter(|&&x| x == 1).count();
+        let tens = z.iter().filter(|&&x| x == 10).count();
+        assert!(ones > tens);
+
+        let few: Vec<u8> = few_unique(100, 3, 5);
+        assert!(few.iter().all(|&x| x < 3));
+        let nearly: Vec<i32> = nearly_sorted(100, 5, 9);
+        assert_eq!(nearly, nearly_sorted::<i32>(100, 5, 9));
+    }
+
+    #[test]
+    fn shapes() {
+        assert_eq!(sorted::<u8>(4), vec![0, 1, 2, 3]);
+        assert_eq!(reversed::<i16>(4), vec![3, 2, 1, 0]);
+        assert_eq!(all_equal::<u64>(3, 7), vec![7, 7, 7]);
+        assert_eq!(sawtooth::<i32>(9, 3), vec![0, 1, 2, 0, 1, 2, 0, 1, 2]);
+        assert_eq!(organ_pipe::<i32>(7), vec![0, 1, 2, 3, 2, 1, 0]);
+        assert_eq!(organ_pipe::<i32>(6), vec![0, 1, 2, 2, 1, 0]);
+        assert!(organ_pipe::<i32>(0).is_empty());
+
+        // 超出范围时取最小值或者最大值
+        assert_eq!(reversed::<u8>(300)[0], u8::MAX);
+        assert_eq!(all_equal::<u8>(1, -3), vec![0]);
+    }
+
+    #[test]
+    fn killer() {
+        let n = 500;
+        let count = |arr: &[usize], sort: SortBy| {
+            let mut calls = 0;
+            let mut arr = arr.to_vec();
+            sort(&mut arr, &mut |a, b| {
+                calls += 1;
+                a.cmp(b)
+            });
+            calls
+        };
+        let quick_v1: SortBy = |arr, compare| quick::sort_v1_by(arr, compare);
+        let merge_v1: SortBy = |arr, compare| merge::sort_v1_by(arr, compare);
+
+        let killer: Vec<usize> = quicksort_killer(n, quick_v1);
+        let mut values = killer.clone();
+        values.sort_unstable();
+        assert_eq!(values, sorted::<usize>(n));
+
+        // 对抗输入上比较次数接近 n^2 / 2, 随机输入上约为 n log n
+        let random: Vec<usize> = uniform(n, 0, n as i64, 1);
+        assert!(count(&killer, quick_v1) >= n * n / 4);
+        assert!(count(&random, quick_v1) < n * n / 20);
+        // 对归并排序没有影响
+        assert!(count(&killer, merge_v1) < n * n / 20);
+
+        // 三数取中选择 pivot 的快速排序同样退化
+        let median_of_3: SortBy = median_of_3_sort;
+        let killer: Vec<usize> = quicksort_killer(n, median_of_3);
+        assert!(count(&killer, median_of_3) >= n * n / 8);
+        assert!(count(&random, median_of_3) < n * n / 20);
+    }
+
+    fn median_of_3_sort(
+        arr: &mut [usize],
+        compare: &mut (dyn FnMut(&usize, &usize) -> Ordering + Send),
+    ) {
+        if arr.len() < 3 {
+            if arr.len() == 2 && compare(&arr[1], &arr[0]) == Ordering::Less {
+                arr.swap(0, 1);
+            }
+            return;
+        }
+
+        // 把 arr[0], arr[mid], arr[last] 的中位数放到 arr[0]
+        let (mid, last) = (arr.len() / 2, arr.len() - 1);
+        if compare(&arr[mid], &arr[0]) == Ordering::Less {
+            arr.swap(mid, 0);
+        }
+        if compare(&arr[last], &arr[0]) == Ordering::Less {
+            arr.swap(last, 0);
+        }
+        if compare(&arr[last], &arr[mid]) == Ordering::Less {
+            arr.swap(last, mid);
+        }
+        arr.swap(0, mid);
+
+        let e = arr[0];
+        let mut j = 0;
+        for i in 1..arr.len() {
+            if compare(&arr[i], &e) == Ordering::Less {
+                j += 1;
+                arr.swap(j, i);
+            }
+        }
+        arr.swap(0, j);
+        median_of_3_sort(&mut arr[..j], compare);
+        median_of_3_sort(&mut arr[j + 1..], compare);
+    }
+}
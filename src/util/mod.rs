@@ -1,15 +1,25 @@
+pub mod generate;
+pub mod random;
 pub mod sst;
 
-use rand::{self, Rng};
+use rand::Rng;
+use std::cmp::Ordering;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 use std::str;
-use std::time::Instant;
 
 // 生成随机数组
 pub fn generate_random_array(total: i32, low: i32, high: i32) -> Vec<i32> {
-    let mut rng = rand::thread_rng();
+    generate_random_array_with_rng(total, low, high, &mut random::rng())
+}
+
+pub fn generate_random_array_with_rng<R: Rng>(
+    total: i32,
+    low: i32,
+    high: i32,
+    rng: &mut R,
+) -> Vec<i32> {
     let mut result = Vec::new();
 
     for _ in 1..total + 1 {
@@ -19,56 +29,29 @@ pub fn generate_random_array(total: i32, low: i32, high: i32) -> Vec<i32> {
     result
 }
 
-// 生成部分有序的数组
-// 首先生成一个含有[0...n-1]的完全有序数组, 之后随机交换swap_times对数据
-// swap_times定义了数组的无序程度
-// swap_times == 0 时, 数组完全有序
-// swap_times 越大, 数组越趋向于无序
-pub fn generate_nearly_ordered_array(total: i32, swap_times: i32) -> Vec<i32> {
-    let mut result = Vec::new();
-    let mut rng = rand::thread_rng();
-
-    for i in 0..total {
-        result.push(i);
-    }
-
-    for _ in 0..swap_times {
-        let pos_x = rng.gen_range(0, total - 1) as usize;
-        let pos_y = rng.gen_range(0, total - 1) as usize;
-        result.swap(pos_x, pos_y);
-    }
-
-    result
+// 判断arr是否有序
+pub fn is_sorted<T: PartialOrd>(arr: &[T]) -> bool {
+    arr.windows(2).all(|pair| pair[0] <= pair[1])
 }
 
-// 判断arr是否有序
-pub fn is_sorted<T: Ord>(arr: &[T]) -> bool {
-    let len = arr.len();
-    for i in 0..len - 1 {
-        if arr[i] > arr[i + 1] {
-            return false;
-        }
-    }
-    true
+// 判断arr是否按照 compare 定义的顺序有序
+pub fn is_sorted_by<T, C>(arr: &[T], compare: C) -> bool
+where
+    C: Fn(&T, &T) -> Ordering,
+{
+    arr.windows(2)
+        .all(|pair| compare(&pair[0], &pair[1]) != Ordering::Greater)
 }
 
-// 测试排序算法的正确性和运行时间
-pub fn test_sort<T, F>(name: &str, f: F, arr: &mut [T])
+// 检查自定义顺序的排序算法是否正确, compare 用于检查结果是否有序
+// 只检查正确性, 运行时间由 sorting::bench 统计
+pub fn test_sort_by<T, F, C>(name: &str, f: F, arr: &mut [T], compare: C)
 where
-    T: Ord,
     F: Fn(&mut [T]),
+    C: Fn(&T, &T) -> Ordering,
 {
-    // 测试运行时间
-    let now = Instant::now();
     f(arr);
-    println!(
-        "| {:<20} | n = {:<8} | {:>12} µs |",
-        name,
-        arr.len(),
-        now.elapsed().as_micros()
-    );
-    // 测试正确性
-    assert_eq!(is_sorted(arr), true);
+    assert!(is_sorted_by(arr, compare), "{} failed", name);
 }
 
 fn first_character_index(s: &str, start: usize) -> usize {
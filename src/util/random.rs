@@ -0,0 +1,87 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cell::RefCell;
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::OnceLock;
+
+// 所有带随机性的代码都从这里获取随机数生成器, 保证结果可以复现
+// 进程内使用同一个 seed, 由环境变量 ALGORITHM_SEED 指定, 没有指定时随机选择
+// 每个线程从 seed 开始维护一个默认的随机数序列, 相同的 seed 和相同的调用顺序得到相同的结果
+
+pub const SEED_ENV: &str = "ALGORITHM_SEED";
+
+static SEED: OnceLock<u64> = OnceLock::new();
+
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::seed_from_u64(seed()));
+}
+
+// 整个进程使用的 seed
+pub fn seed() -> u64 {
+    *SEED.get_or_init(|| match env::var(SEED_ENV) {
+        Ok(s) => s
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("{} must be an u64: {:?}", SEED_ENV, s)),
+        Err(_) => rand::random(),
+    })
+}
+
+// 重新设置当前线程的默认随机数序列
+pub fn reseed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+// 从当前线程的默认随机数序列派生一个新的随机数生成器
+pub fn rng() -> StdRng {
+    RNG.with(|rng| StdRng::from_rng(&mut *rng.borrow_mut()).unwrap())
+}
+
+// 从 seed 开始运行 f, panic 时打印复现所需的 seed
+// 测试中使用, 失败后设置 ALGORITHM_SEED 重新运行即可复现
+pub fn reproducible<F: FnOnce() -> R, R>(f: F) -> R {
+    let seed = seed();
+    reseed(seed);
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("failed with seed {0}, rerun with {1}={0}", seed, SEED_ENV);
+            panic::resume_unwind(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn reproducible_sequence() {
+        let first: Vec<u32> = reproducible(|| {
+            let mut rng = rng();
+            (0..10).map(|_| rng.gen()).collect()
+        });
+        let second: Vec<u32> = reproducible(|| {
+            let mut rng = rng();
+            (0..10).map(|_| rng.gen()).collect()
+        });
+        assert_eq!(first, second);
+
+        // 同一个序列派生的随机数生成器互不相同
+        let (a, b): (u64, u64) = reproducible(|| (rng().gen(), rng().gen()));
+        assert_ne!(a, b);
+
+        reseed(42);
+        let a: u64 = rng().gen();
+        reseed(42);
+        assert_eq!(a, rng().gen::<u64>());
+    }
+
+    #[test]
+    fn panic_is_propagated() {
+        let r = panic::catch_unwind(|| reproducible(|| panic!("boom")));
+        assert!(r.is_err());
+    }
+}